@@ -1,21 +1,22 @@
 use ark_ff::{
     BigInteger64 as BigInteger, FftParameters, Field, Fp64, Fp64Parameters, FpParameters,
+    UniformRand,
 };
 use ark_std::test_rng;
 use criterion::{black_box, BenchmarkId, Criterion};
 use rand::{CryptoRng, Rng, RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
 
-use fss_funcs::{interval, point, Seed, FSS};
+use fss_funcs::{interval, point, FixedKeyAes, Seed, TreeFSS, TreeScheme, FSS};
 
 #[macro_use]
 extern crate criterion;
 
 const LOG_DOMAIN_RANGE: [usize; 3] = [20, 25, 30];
+const FULL_EVAL_LOG_DOMAIN_RANGE: [usize; 3] = [10, 15, 20];
 
-// Set field, seed, and PRG types
+// Set field and PRG types
 type F = Fp64<FParameters>;
-type S = [u8; 32];
 type PRG = ChaChaRng;
 
 // Define a field to use. This is the same 63-bit field used in
@@ -110,14 +111,67 @@ where
     group.finish();
 }
 
+/// Bench the `full_eval()` function for tree-based point functions
+fn full_eval_point_bench<T>(c: &mut Criterion, func: &str)
+where
+    T: TreeFSS<F, PRG, Description = (usize, usize, F), Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+{
+    let mut rng = test_rng();
+
+    let mut group = c.benchmark_group(format!("{}/FullEval", func));
+
+    for log_domain in FULL_EVAL_LOG_DOMAIN_RANGE {
+        // Generate a random point in the given domain and field value
+        let x = rng.gen_range(0..2usize.pow(log_domain as u32));
+        let y = F::rand(&mut rng);
+        let func = (log_domain, x, y);
+
+        // Generate keys
+        let (k1, _) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("P1", log_domain), &log_domain, |b, _| {
+            b.iter(|| black_box(TreeScheme::<F, PRG, T>::full_eval(&k1)))
+        });
+
+        #[cfg(feature = "parallel")]
+        group.bench_with_input(
+            BenchmarkId::new("P1/Parallel", log_domain),
+            &log_domain,
+            |b, _| b.iter(|| black_box(TreeScheme::<F, PRG, T>::par_full_eval(&k1, 8))),
+        );
+    }
+    group.finish();
+}
+
 fn bench_dpf(c: &mut Criterion) {
-    gen_point_bench::<F, point::bgi15::Bgi15DPF<F, PRG, S>>(c, "Point");
-    eval_point_bench::<F, point::bgi15::Bgi15DPF<F, PRG, S>>(c, "Point");
+    gen_point_bench::<F, point::bgi15::Bgi15DPF<F, PRG>>(c, "Point");
+    eval_point_bench::<F, point::bgi15::Bgi15DPF<F, PRG>>(c, "Point");
+    full_eval_point_bench::<point::bgi15::Bgi15<F, PRG>>(c, "Point");
+    gen_point_bench::<F, point::bgi15::Bgi15DPF<F, FixedKeyAes>>(c, "Point/AES");
+    eval_point_bench::<F, point::bgi15::Bgi15DPF<F, FixedKeyAes>>(c, "Point/AES");
+
+    gen_point_bench::<F, point::bgi16::Bgi16DPF<F, PRG>>(c, "Point/BGI16");
+    eval_point_bench::<F, point::bgi16::Bgi16DPF<F, PRG>>(c, "Point/BGI16");
+    full_eval_point_bench::<point::bgi16::Bgi16<F, PRG>>(c, "Point/BGI16");
+    gen_point_bench::<F, point::bgi16::Bgi16DPF<F, FixedKeyAes>>(c, "Point/BGI16/AES");
+    eval_point_bench::<F, point::bgi16::Bgi16DPF<F, FixedKeyAes>>(c, "Point/BGI16/AES");
 }
 
 fn bench_dif(c: &mut Criterion) {
-    gen_point_bench::<F, interval::bgi15::Bgi15DIF<F, PRG, S>>(c, "Interval");
-    eval_point_bench::<F, interval::bgi15::Bgi15DIF<F, PRG, S>>(c, "Interval");
+    gen_point_bench::<F, interval::bgi15::Bgi15DIF<F, PRG>>(c, "Interval");
+    eval_point_bench::<F, interval::bgi15::Bgi15DIF<F, PRG>>(c, "Interval");
+    full_eval_point_bench::<interval::bgi15::Bgi15<F, PRG>>(c, "Interval");
+    gen_point_bench::<F, interval::bgi15::Bgi15DIF<F, FixedKeyAes>>(c, "Interval/AES");
+    eval_point_bench::<F, interval::bgi15::Bgi15DIF<F, FixedKeyAes>>(c, "Interval/AES");
+
+    gen_point_bench::<F, interval::bcg21::Bcg21DIF<F, PRG>>(c, "Interval/BCG21");
+    eval_point_bench::<F, interval::bcg21::Bcg21DIF<F, PRG>>(c, "Interval/BCG21");
+    full_eval_point_bench::<interval::bcg21::Bcg21<F, PRG>>(c, "Interval/BCG21");
+    gen_point_bench::<F, interval::bcg21::Bcg21DIF<F, FixedKeyAes>>(c, "Interval/BCG21/AES");
+    eval_point_bench::<F, interval::bcg21::Bcg21DIF<F, FixedKeyAes>>(c, "Interval/BCG21/AES");
 }
 
 criterion_group!(benches, bench_dpf, bench_dif);
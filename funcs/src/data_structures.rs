@@ -64,7 +64,7 @@ impl<T: Serialize> Serialize for Pair<T> {
     }
 }
 
-impl<T: Deserialize + Copy + Default> Deserialize for Pair<T> {
+impl<T: Deserialize + Copy> Deserialize for Pair<T> {
     #[inline]
     default fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
         Ok(Pair(<[T; 2]>::deserialize(&mut reader)?))
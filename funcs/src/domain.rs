@@ -0,0 +1,421 @@
+//! A module providing the domains which tree-based FSS schemes can be defined over
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, ops::Range, vec::Vec};
+
+use crate::{
+    tree::{TreeFSS, TreeKey, TreeScheme},
+    AbelianGroup, TreePrg, FSS,
+};
+
+/// Outputs the last point `2^log_domain - 1` of the domain of size `2^log_domain`, which must be
+/// indexed by `usize`
+#[inline]
+pub(crate) fn last_point(log_domain: usize) -> Result<usize, Box<dyn Error>> {
+    if log_domain > usize::BITS as usize {
+        return Err("Domain is too large to be indexed by usize".into());
+    }
+    Ok(domain_mask(log_domain))
+}
+
+/// Outputs the size `2^log_domain` of a domain whose points are all evaluated at once, which must
+/// fit in a `usize`
+#[inline]
+pub(crate) fn domain_size(log_domain: usize) -> Result<usize, Box<dyn Error>> {
+    last_point(log_domain)?
+        .checked_add(1)
+        .ok_or_else(|| "Domain is too large to be fully evaluated".into())
+}
+
+/// Ensures that `point` is contained in the domain of size `2^log_domain`
+#[inline]
+pub(crate) fn check_point(log_domain: usize, point: usize) -> Result<(), Box<dyn Error>> {
+    if point > last_point(log_domain)? {
+        return Err("Input point is not contained in provided domain".into());
+    }
+    Ok(())
+}
+
+/// Outputs the mask of the low `log_domain` bits, which reduces a `usize` modulo `2^log_domain`
+#[inline]
+fn domain_mask(log_domain: usize) -> usize {
+    usize::MAX
+        .checked_shr(usize::BITS.saturating_sub(log_domain as u32))
+        .unwrap_or(0)
+}
+
+/// Outputs `a + b` in `Z_{2^log_domain}`
+#[inline]
+pub(crate) fn add_mod(log_domain: usize, a: usize, b: usize) -> usize {
+    a.wrapping_add(b) & domain_mask(log_domain)
+}
+
+/// Outputs `a - b` in `Z_{2^log_domain}`
+#[inline]
+pub(crate) fn sub_mod(log_domain: usize, a: usize, b: usize) -> usize {
+    a.wrapping_sub(b) & domain_mask(log_domain)
+}
+
+/// A point in a domain `{0, 1}^n` of a tree-based FSS scheme, which selects a path in the tree
+/// by its bit decomposition.
+pub trait TreeDomain {
+    /// Outputs the big-endian bit decomposition of `self` in the domain of `2^log_domain` points
+    fn to_bits(&self, log_domain: usize) -> Result<Vec<bool>, Box<dyn Error>>;
+}
+
+impl TreeDomain for usize {
+    #[inline]
+    fn to_bits(&self, log_domain: usize) -> Result<Vec<bool>, Box<dyn Error>> {
+        crate::usize_to_bits(log_domain, *self)
+    }
+}
+
+impl TreeDomain for u128 {
+    #[inline]
+    fn to_bits(&self, log_domain: usize) -> Result<Vec<bool>, Box<dyn Error>> {
+        le_bytes_to_bits(log_domain, &self.to_le_bytes())
+    }
+}
+
+/// Helper function to convert an integer given by its little-endian bytes to a vector of bools
+/// in big endian format. The domain can be larger than the integer type, in which case the high
+/// bits are 0.
+#[inline]
+pub(crate) fn le_bytes_to_bits(
+    log_domain: usize,
+    bytes: &[u8],
+) -> Result<Vec<bool>, Box<dyn Error>> {
+    // Ensure that the point is valid in the given domain, i.e. it has no bits set at or above
+    // `log_domain`
+    let bit = |i: usize| {
+        bytes
+            .get(i / 8)
+            .is_some_and(|byte| byte >> (i % 8) & 1 == 1)
+    };
+    if (log_domain..8 * bytes.len()).any(bit) {
+        return Err("Input point is not contained in provided domain".into());
+    }
+
+    // Compute the big-endian bit-decomposition
+    Ok((0..log_domain).rev().map(bit).collect())
+}
+
+/// An arbitrary-length bit string, e.g. a hash or IPv6 address, which is a point in the domain
+/// `{0, 1}^n` where `n` is its length. The bits are big-endian: the first bit is the most
+/// significant bit of the first byte. Interval functions over bit strings compare them
+/// lexicographically.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitString {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitString {
+    /// Creates the bit string consisting of the first `len` bits of `bytes`
+    pub fn new(bytes: Vec<u8>, len: usize) -> Result<Self, Box<dyn Error>> {
+        if len > 8 * bytes.len() {
+            return Err("BitString(): Length is longer than the provided bytes".into());
+        }
+        Ok(Self { bytes, len })
+    }
+
+    /// Creates the bit string consisting of all bits of `bytes`
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        let len = 8 * bytes.len();
+        Self { bytes, len }
+    }
+
+    /// Outputs the number of bits in the bit string
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Outputs whether the bit string is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Outputs the `i`-th bit of the bit string
+    #[inline]
+    pub fn bit(&self, i: usize) -> bool {
+        assert!(i < self.len);
+        self.bytes[i / 8] >> (7 - i % 8) & 1 == 1
+    }
+}
+
+impl TreeDomain for BitString {
+    #[inline]
+    fn to_bits(&self, log_domain: usize) -> Result<Vec<bool>, Box<dyn Error>> {
+        if self.len != log_domain {
+            return Err("Input point is not contained in provided domain".into());
+        }
+        Ok((0..self.len).map(|i| self.bit(i)).collect())
+    }
+}
+
+/// The description of a point or interval function over the domain `[0, N)`: the domain size
+/// `N`, a point in that domain, and the value of the function.
+pub(crate) type BoundedDescription<F> = (usize, usize, F);
+
+/// A point or interval function scheme over the domain `[0, N)` for any `N`, built from a scheme
+/// `S` over the domain `{0, 1}^n` for `n = ceil(log2(N))`.
+///
+/// Keys remember `N`, so `gen` and `eval` reject points outside of `[0, N)`, and full-domain
+/// evaluation of tree-based schemes outputs exactly `N` shares without expanding the subtrees
+/// which only contain padding.
+pub struct Bounded<F, S>
+where
+    F: AbelianGroup,
+    S: FSS<Description = (usize, usize, F), Domain = usize, Range = F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _fss: PhantomData<S>,
+}
+
+/// A key for a function over the domain `[0, N)`
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BoundedKey<K: Serialize + Deserialize> {
+    pub key: K,
+    pub domain_size: usize,
+}
+
+impl<F, S> FSS for Bounded<F, S>
+where
+    F: AbelianGroup,
+    S: FSS<Description = (usize, usize, F), Domain = usize, Range = F, Share = F>,
+{
+    type Key = BoundedKey<S::Key>;
+    type Description = BoundedDescription<F>;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (domain_size, x, val) = *f;
+        if x >= domain_size {
+            return Err("Input point is not contained in provided domain".into());
+        }
+
+        // The smallest tree with at least `domain_size` leaves. Trees have at least one level.
+        let log_domain = (usize::BITS - (domain_size - 1).leading_zeros()).max(1) as usize;
+        let (p1_key, p2_key) = S::gen(&(log_domain, x, val), rng)?;
+        Ok((
+            BoundedKey {
+                key: p1_key,
+                domain_size,
+            },
+            BoundedKey {
+                key: p2_key,
+                domain_size,
+            },
+        ))
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        if *point >= key.domain_size {
+            return Err("Input point is not contained in provided domain".into());
+        }
+        S::eval(&key.key, point)
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        S::decode(shares)
+    }
+}
+
+impl<F, P, T> Bounded<F, TreeScheme<F, P, T>>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P, Description = (usize, usize, F), Domain = usize>,
+{
+    /// Takes a `Key` as input, and outputs a secret share of the underlying function at every
+    /// point in `[0, N)`, ordered by point.
+    pub fn full_eval(key: &BoundedKey<TreeKey<F, P, T>>) -> Result<Vec<F>, Box<dyn Error>> {
+        TreeScheme::<F, P, T>::eval_range(&key.key, 0..key.domain_size)
+    }
+
+    /// Takes a `Key` and a range of points as input, and outputs a secret share of the underlying
+    /// function at each point in the range, ordered by point.
+    pub fn eval_range(
+        key: &BoundedKey<TreeKey<F, P, T>>,
+        range: Range<usize>,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        Self::check_range(key, &range)?;
+        TreeScheme::<F, P, T>::eval_range(&key.key, range)
+    }
+
+    /// Takes a `Key` and a batch of points as input, and outputs a secret share of the underlying
+    /// function at each point, in the same order as `points`.
+    pub fn batch_eval(
+        key: &BoundedKey<TreeKey<F, P, T>>,
+        points: &[usize],
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        Self::check_points(key, points)?;
+        TreeScheme::<F, P, T>::batch_eval(&key.key, points)
+    }
+
+    /// Ensures that `range` is contained in `[0, N)`
+    fn check_range(
+        key: &BoundedKey<TreeKey<F, P, T>>,
+        range: &Range<usize>,
+    ) -> Result<(), Box<dyn Error>> {
+        if range.start > range.end || range.end > key.domain_size {
+            return Err("Input range is not contained in provided domain".into());
+        }
+        Ok(())
+    }
+
+    /// Ensures that `points` are contained in `[0, N)`
+    fn check_points(
+        key: &BoundedKey<TreeKey<F, P, T>>,
+        points: &[usize],
+    ) -> Result<(), Box<dyn Error>> {
+        if points.iter().any(|p| *p >= key.domain_size) {
+            return Err("Input point is not contained in provided domain".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<F, P, T> Bounded<F, TreeScheme<F, P, T>>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P, Description = (usize, usize, F), Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+{
+    /// A multi-threaded version of `full_eval`. See `TreeScheme::par_full_eval`.
+    pub fn par_full_eval(
+        key: &BoundedKey<TreeKey<F, P, T>>,
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        TreeScheme::<F, P, T>::par_eval_range(&key.key, 0..key.domain_size, split_depth)
+    }
+
+    /// A multi-threaded version of `eval_range`. See `TreeScheme::par_eval_range`.
+    pub fn par_eval_range(
+        key: &BoundedKey<TreeKey<F, P, T>>,
+        range: Range<usize>,
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        Self::check_range(key, &range)?;
+        TreeScheme::<F, P, T>::par_eval_range(&key.key, range, split_depth)
+    }
+
+    /// A multi-threaded version of `batch_eval`. See `TreeScheme::par_batch_eval`.
+    pub fn par_batch_eval(
+        key: &BoundedKey<TreeKey<F, P, T>>,
+        points: &[usize],
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        Self::check_points(key, points)?;
+        TreeScheme::<F, P, T>::par_batch_eval(&key.key, points, split_depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+    use rand::Rng;
+    use rand_chacha::ChaChaRng;
+
+    use super::{BitString, Bounded, TreeDomain};
+    use crate::{interval::bcg21::Bcg21DIF, point::bgi16::Bgi16DPF, AbelianGroup, Z2k, FSS};
+
+    type R = Z2k<u64>;
+    type BoundedDPF = Bounded<R, Bgi16DPF<R, ChaChaRng>>;
+    type BoundedDIF = Bounded<R, Bcg21DIF<R, ChaChaRng>>;
+
+    #[test]
+    fn test_to_bits() {
+        // Integers are decomposed in big-endian order
+        assert_eq!(5usize.to_bits(4).unwrap(), vec![false, true, false, true]);
+        assert_eq!(5u128.to_bits(4).unwrap(), vec![false, true, false, true]);
+        assert!(16usize.to_bits(4).is_err());
+        assert!(16u128.to_bits(4).is_err());
+
+        // Domains can be larger than 64 bits
+        let x = u128::MAX - 1;
+        let bits = x.to_bits(128).unwrap();
+        assert_eq!(bits.len(), 128);
+        assert!(bits[..127].iter().all(|b| *b) && !bits[127]);
+        let bits = 1usize.to_bits(100).unwrap();
+        assert!(bits[..99].iter().all(|b| !*b) && bits[99]);
+        assert!((1u128 << 100).to_bits(100).is_err());
+
+        // Bit strings are decomposed in order, and must have the same length as the domain
+        let x = BitString::new(vec![0b1010_0000, 0b1100_0000], 10).unwrap();
+        let expected = [
+            true, false, true, false, false, false, false, false, true, true,
+        ];
+        assert_eq!(x.to_bits(10).unwrap(), expected);
+        assert!(x.to_bits(16).is_err());
+        assert!(BitString::new(vec![0], 9).is_err());
+        assert_eq!(
+            BitString::from_bytes(vec![0xff; 32]).to_bits(256).unwrap(),
+            [true; 256]
+        );
+    }
+
+    #[test]
+    fn test_bounded_domains() {
+        let mut rng = test_rng();
+
+        for domain_size in [1usize, 2, 3, 5, 100, 1000, 1024, 1025] {
+            let x = rng.gen_range(0..domain_size);
+            let y = R::group_sample(&mut rng);
+
+            // The tree is the smallest which covers the domain
+            let (p1_dpf, p2_dpf) = BoundedDPF::gen(&(domain_size, x, y), &mut rng).unwrap();
+            let (p1_dif, p2_dif) = BoundedDIF::gen(&(domain_size, x, y), &mut rng).unwrap();
+            let log_domain = (domain_size as f64).log2().ceil().max(1.0) as usize;
+            assert_eq!(p1_dpf.key.log_domain, log_domain);
+
+            // Full-domain evaluation outputs exactly `domain_size` shares
+            let p1_results = BoundedDPF::full_eval(&p1_dpf).unwrap();
+            let p2_results = BoundedDPF::full_eval(&p2_dpf).unwrap();
+            assert_eq!(p1_results.len(), domain_size);
+            for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+                assert_eq!(*p1_result, BoundedDPF::eval(&p1_dpf, &p).unwrap());
+                let expected = if p == x { y } else { R::group_zero() };
+                assert_eq!(
+                    BoundedDPF::decode((p1_result, p2_result)).unwrap(),
+                    expected
+                );
+            }
+            #[cfg(feature = "parallel")]
+            assert_eq!(BoundedDPF::par_full_eval(&p1_dpf, 4).unwrap(), p1_results);
+
+            let p1_results = BoundedDIF::full_eval(&p1_dif).unwrap();
+            let p2_results = BoundedDIF::full_eval(&p2_dif).unwrap();
+            assert_eq!(p1_results.len(), domain_size);
+            for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+                assert_eq!(*p1_result, BoundedDIF::eval(&p1_dif, &p).unwrap());
+                let expected = if p < x { y } else { R::group_zero() };
+                assert_eq!(
+                    BoundedDIF::decode((p1_result, p2_result)).unwrap(),
+                    expected
+                );
+            }
+
+            // Points outside of `[0, domain_size)` are rejected, even if they are in the tree
+            assert!(BoundedDPF::gen(&(domain_size, domain_size, y), &mut rng).is_err());
+            assert!(BoundedDPF::eval(&p1_dpf, &domain_size).is_err());
+            assert!(BoundedDPF::eval_range(&p1_dpf, 0..domain_size + 1).is_err());
+            assert!(BoundedDPF::batch_eval(&p1_dpf, &[0, domain_size]).is_err());
+            assert!(BoundedDIF::eval(&p2_dif, &domain_size).is_err());
+        }
+
+        // The domain can't be empty
+        assert!(BoundedDPF::gen(&(0, 0, R::group_zero()), &mut rng).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData};
+
+use crate::{
+    gates::GateDescription,
+    point::{bgi15, bgi16, DPF},
+    Ring, FSS,
+};
+
+/// Equality gate built from the DPF of [[BGI15]].
+///
+/// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
+pub type Bgi15Equality<F, P> = Equality<F, bgi15::Bgi15DPF<F, P>>;
+
+/// Equality gate built from the DPF of [[BGI16]].
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub type Bgi16Equality<F, P> = Equality<F, bgi16::Bgi16DPF<F, P>>;
+
+/// An equality (zero-test) gate, which outputs shares of `[x == 0]` on the masked input `x + r`.
+///
+/// This is a single key of the `DPF` `D` at the mask `r`. Since the masked input is public, the
+/// same key also tests `x` for equality with any public `c` when evaluated on `x + r - c`.
+pub struct Equality<F, D>
+where
+    F: Ring,
+    D: DPF<F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _dpf: PhantomData<D>,
+}
+
+impl<F, D> FSS for Equality<F, D>
+where
+    F: Ring,
+    D: DPF<F, Share = F>,
+{
+    type Key = D::Key;
+    type Description = GateDescription;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, mask) = *f;
+        D::gen(&(log_domain, mask, F::one()), rng)
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        D::eval(key, point)
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        D::decode(shares)
+    }
+}
@@ -0,0 +1,119 @@
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, vec::Vec};
+
+use crate::{
+    gates::{check_point, less_than, share},
+    interval::{bcg21, bgi15, DIF},
+    Ring, FSS,
+};
+
+/// MIC gate built from the DIF of [[BGI15]].
+///
+/// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
+pub type Bgi15Mic<F, P> = Mic<F, bgi15::Bgi15DIF<F, P>>;
+
+/// MIC gate built from the DIF of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21Mic<F, P> = Mic<F, bcg21::Bcg21DIF<F, P>>;
+
+/// The description of a MIC gate: the logarithm of the domain size, a list of public intervals
+/// `[p, q]` in that domain, and the input mask `r`. An interval wraps around the end of the
+/// domain when `p > q`.
+pub type MicDescription = (usize, Vec<(usize, usize)>, usize);
+
+/// A multiple interval containment (MIC) gate, which outputs shares of `[p <= x <= q]` for each
+/// public interval `[p, q]` on the masked input `x + r`.
+///
+/// All of the intervals are evaluated using a single key of the `DIF` `D` at the mask `r`, with
+/// two evaluations per interval.
+pub struct Mic<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+/// A key for a MIC gate. The intervals are public, and `one` is a share of 1.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MicKey<F: Ring, K: Serialize + Deserialize> {
+    pub log_domain: usize,
+    pub intervals: Vec<(usize, usize)>,
+    pub key: K,
+    pub one: F,
+}
+
+impl<F, D> FSS for Mic<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    type Key = MicKey<F, D::Key>;
+    type Description = MicDescription;
+    type Domain = usize;
+    type Range = Vec<F>;
+    type Share = Vec<F>;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, ref intervals, mask) = *f;
+        for &(p, q) in intervals {
+            check_point(log_domain, p)?;
+            check_point(log_domain, q)?;
+        }
+
+        let (p1_key, p2_key) = D::gen(&(log_domain, mask, F::one()), rng)?;
+        let (p1_one, p2_one) = share(F::one(), rng);
+
+        Ok((
+            MicKey {
+                log_domain,
+                intervals: intervals.clone(),
+                key: p1_key,
+                one: p1_one,
+            },
+            MicKey {
+                log_domain,
+                intervals: intervals.clone(),
+                key: p2_key,
+                one: p2_one,
+            },
+        ))
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<Vec<F>, Box<dyn Error>> {
+        check_point(key.log_domain, *point)?;
+
+        // `[p <= x <= q] = [x < q + 1] - [x < p]`, plus 1 if the interval wraps around. The share
+        // of `[x + r < r]` used by each comparison cancels out, so it is never evaluated.
+        let less_than =
+            |c| less_than::<F, D>(&key.key, &key.one, key.log_domain, *point, &F::zero(), c);
+        key.intervals
+            .iter()
+            .map(|&(p, q)| {
+                let wrap = match p > q {
+                    true => key.one,
+                    false => F::zero(),
+                };
+                Ok(less_than(q as u128 + 1)? - less_than(p as u128)? + wrap)
+            })
+            .collect()
+    }
+
+    fn decode(shares: (&Vec<F>, &Vec<F>)) -> Result<Vec<F>, Box<dyn Error>> {
+        if shares.0.len() != shares.1.len() {
+            return Err("Shares have different lengths".into());
+        }
+        Ok(shares
+            .0
+            .iter()
+            .zip(shares.1)
+            .map(|(s1, s2)| *s1 - s2)
+            .collect())
+    }
+}
@@ -0,0 +1,92 @@
+//! A module implementing FSS gates for secure computation following [[BCG+21]].
+//!
+//! A gate secret-shares a public function `f` applied to a secret input `x` in the ring
+//! `Z_{2^n}`. The dealer samples a random mask `r`, and the parties evaluate their keys on the
+//! public masked input `x + r mod 2^n` to get secret shares of `f(x)`. Most gates are built from
+//! `DIF` keys at the mask, which give shares of `[x < c]` for any public `c` (see `less_than`),
+//! while the equality gate uses a `DPF` key at the mask.
+//!
+//! [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+use rand::{CryptoRng, RngCore};
+use std::error::Error;
+
+use crate::{interval::DIF, AbelianGroup, Ring};
+
+pub(crate) use crate::domain::{add_mod, check_point, last_point, sub_mod};
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+/// Equality (zero-test) gate.
+pub mod equality;
+
+/// ReLU, sign bit, and non-negativity gates.
+pub mod relu;
+
+/// Multiple interval containment gate.
+pub mod mic;
+
+/// Offset gates with input and output masks for any shiftable FSS scheme.
+pub mod offset;
+
+/// Piecewise-polynomial (spline) gate.
+pub mod spline;
+
+/// Right shift (truncation) and bit decomposition gates.
+pub mod truncation;
+
+/// The description of a gate for a fixed function: the logarithm of the domain size, and the
+/// input mask `r`
+pub type GateDescription = (usize, usize);
+
+/// Samples shares of `val`, i.e. a pair whose difference is `val`
+#[inline]
+pub(crate) fn share<G: AbelianGroup, RNG: CryptoRng + RngCore>(val: G, rng: &mut RNG) -> (G, G) {
+    let p2_share = G::group_sample(rng);
+    (p2_share.group_add(&val), p2_share)
+}
+
+/// Outputs the integer `x` as an element of the ring `F`, i.e. the sum of `x` copies of 1. Unlike
+/// converting from `u64`, this works for every ring, and for values such as `2^64`.
+#[inline]
+pub(crate) fn from_u128<F: Ring>(x: u128) -> F {
+    (0..u128::BITS - x.leading_zeros())
+        .rev()
+        .fold(F::zero(), |acc, i| {
+            let double = acc + acc;
+            match (x >> i) & 1 == 1 {
+                true => double + F::one(),
+                false => double,
+            }
+        })
+}
+
+/// Takes a party's `DIF` key for `[t < r]`, where `r` is the input mask, and their share of 1,
+/// and outputs their share of `[x < c]` for a public `c` in `0..=2^log_domain`, where `masked` is
+/// `x + r`. This relies on `[x < c] = [masked - c < r] - [masked < r] + [masked < c]`, where the
+/// subtraction is in `Z_{2^log_domain}`.
+///
+/// The share of `[masked < r]` doesn't depend on `c`, so it is passed in by the caller, since
+/// it is often cancelled out or reused across many comparisons.
+#[inline]
+pub(crate) fn less_than<F, D>(
+    key: &D::Key,
+    one: &F,
+    log_domain: usize,
+    masked: usize,
+    wrap: &F,
+    c: u128,
+) -> Result<F, Box<dyn Error>>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    // `c` may be `2^log_domain` itself, which is 0 in `Z_{2^log_domain}` but doesn't fit in `usize`
+    // when `log_domain` is 64.
+    let shifted = sub_mod(log_domain, masked, c as usize);
+    let public = match (masked as u128) < c {
+        true => *one,
+        false => F::zero(),
+    };
+    Ok(D::eval(key, &shifted)? - wrap + public)
+}
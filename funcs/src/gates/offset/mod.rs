@@ -0,0 +1,136 @@
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData};
+
+use crate::{
+    gates::{add_mod, check_point, share},
+    interval::{two_sided::TwoSided, DIF},
+    point::{bgi15, bgi16},
+    AbelianGroup, Ring, TreePrg, FSS,
+};
+
+/// An FSS scheme whose function descriptions can be shifted along the domain, i.e. for any
+/// description of `f` and `r`, there is a description of `x -> f(x - r)`.
+pub trait Shift: FSS<Domain = usize> {
+    /// Outputs the description of `f` shifted by `r`, which evaluates to `f(x)` on `x + r`.
+    fn shift(f: &Self::Description, r: usize) -> Result<Self::Description, Box<dyn Error>>;
+}
+
+/// Shifts the point `(log_domain, x, y)` by `r`
+#[inline]
+fn shift_point<F: AbelianGroup>(
+    f: &(usize, usize, F),
+    r: usize,
+) -> Result<(usize, usize, F), Box<dyn Error>> {
+    let (log_domain, x, y) = *f;
+    check_point(log_domain, x)?;
+    check_point(log_domain, r)?;
+    Ok((log_domain, add_mod(log_domain, x, r), y))
+}
+
+impl<F, P> Shift for bgi15::Bgi15DPF<F, P>
+where
+    F: Field + AbelianGroup,
+    P: TreePrg,
+{
+    fn shift(f: &Self::Description, r: usize) -> Result<Self::Description, Box<dyn Error>> {
+        shift_point(f, r)
+    }
+}
+
+impl<F, P> Shift for bgi16::Bgi16DPF<F, P>
+where
+    F: Ring,
+    P: TreePrg,
+{
+    fn shift(f: &Self::Description, r: usize) -> Result<Self::Description, Box<dyn Error>> {
+        shift_point(f, r)
+    }
+}
+
+/// One-sided intervals aren't closed under shifting, but two-sided intervals are since they may
+/// wrap around the end of the domain.
+impl<F, D> Shift for TwoSided<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    fn shift(f: &Self::Description, r: usize) -> Result<Self::Description, Box<dyn Error>> {
+        let (log_domain, a, b, y) = *f;
+        check_point(log_domain, a)?;
+        check_point(log_domain, b)?;
+        check_point(log_domain, r)?;
+        Ok((
+            log_domain,
+            add_mod(log_domain, a, r),
+            add_mod(log_domain, b, r),
+            y,
+        ))
+    }
+}
+
+/// The description of an offset gate: the description of the underlying function, the input
+/// mask `r_in`, and the output mask `r_out`.
+pub type OffsetDescription<D, F> = (D, usize, F);
+
+/// An offset gate, which turns the `Shift`able scheme `S` into a gate in the FSS-based
+/// preprocessing model: evaluating a key on the public masked input `x + r_in` outputs shares of
+/// `f(x) + r_out`.
+///
+/// The keys are for `f` shifted by `r_in`, along with shares of `r_out`.
+pub struct Offset<F, S>
+where
+    F: Ring,
+    S: Shift<Range = F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _scheme: PhantomData<S>,
+}
+
+/// A key for an offset gate. A party's share is their share of the shifted function plus `mask`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OffsetKey<F: Ring, K: Serialize + Deserialize> {
+    pub key: K,
+    pub mask: F,
+}
+
+impl<F, S> FSS for Offset<F, S>
+where
+    F: Ring,
+    S: Shift<Range = F, Share = F>,
+{
+    type Key = OffsetKey<F, S::Key>;
+    type Description = OffsetDescription<S::Description, F>;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (ref f, input_mask, output_mask) = *f;
+        let (p1_key, p2_key) = S::gen(&S::shift(f, input_mask)?, rng)?;
+        let (p1_mask, p2_mask) = share(output_mask, rng);
+
+        Ok((
+            OffsetKey {
+                key: p1_key,
+                mask: p1_mask,
+            },
+            OffsetKey {
+                key: p2_key,
+                mask: p2_mask,
+            },
+        ))
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        Ok(S::eval(&key.key, point)? + key.mask)
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        S::decode(shares)
+    }
+}
@@ -0,0 +1,167 @@
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData};
+
+use crate::{
+    gates::{
+        last_point,
+        mic::{Mic, MicKey},
+        spline::{Spline, SplineKey},
+        GateDescription,
+    },
+    interval::{bcg21, DIF},
+    AbelianGroup, Ring, FSS,
+};
+
+/// ReLU gate built from the DIF of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21Relu<F, P> = Relu<F, bcg21::Bcg21DIF<[F; 2], P>>;
+
+/// Sign bit gate built from the DIF of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21SignBit<F, P> = SignBit<F, bcg21::Bcg21DIF<F, P>>;
+
+/// Non-negativity gate built from the DIF of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21NonNegative<F, P> = NonNegative<F, bcg21::Bcg21DIF<F, P>>;
+
+/// A ReLU gate, which outputs shares of `max(x, 0)` on the masked input `x + r`, where `x` is
+/// interpreted as a signed (two's complement) value in `Z_{2^n}`.
+///
+/// This is a spline gate with a linear piece for the non-negative inputs and a zero piece for
+/// the negative inputs, so `D` is a `DIF` whose range is the two coefficients of the pieces.
+pub struct Relu<F, D>
+where
+    F: Ring,
+    [F; 2]: AbelianGroup,
+    D: DIF<[F; 2], Share = [F; 2]>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+impl<F, D> FSS for Relu<F, D>
+where
+    F: Ring,
+    [F; 2]: AbelianGroup,
+    D: DIF<[F; 2], Share = [F; 2]>,
+{
+    type Key = SplineKey<F, D::Key, 2>;
+    type Description = GateDescription;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, mask) = *f;
+        if log_domain == 0 {
+            return Err("Relu(): Domain must contain at least two points".into());
+        }
+        let half = last_point(log_domain)? / 2 + 1;
+        let pieces = vec![(0, [F::zero(), F::one()]), (half, [F::zero(); 2])];
+        Spline::<F, D, 2>::gen(&(log_domain, pieces, mask), rng)
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        Spline::<F, D, 2>::eval(key, point)
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        Spline::<F, D, 2>::decode(shares)
+    }
+}
+
+/// A sign bit gate, which outputs shares of the most significant bit of `x` on the masked input
+/// `x + r`, i.e. `[x < 0]` when `x` is interpreted as a signed (two's complement) value.
+///
+/// This is a MIC gate for the single interval `[2^{n - 1}, 2^n - 1]`.
+pub struct SignBit<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+impl<F, D> FSS for SignBit<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    type Key = MicKey<F, D::Key>;
+    type Description = GateDescription;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, mask) = *f;
+        if log_domain == 0 {
+            return Err("SignBit(): Domain must contain at least two points".into());
+        }
+        let last = last_point(log_domain)?;
+        Mic::<F, D>::gen(&(log_domain, vec![(last / 2 + 1, last)], mask), rng)
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        Ok(Mic::<F, D>::eval(key, point)?[0])
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        Ok(*shares.0 - shares.1)
+    }
+}
+
+/// A non-negativity gate, which outputs shares of `[x >= 0]` on the masked input `x + r`, where
+/// `x` is interpreted as a signed (two's complement) value in `Z_{2^n}`.
+///
+/// This is a MIC gate for the single interval `[0, 2^{n - 1} - 1]`.
+pub struct NonNegative<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+impl<F, D> FSS for NonNegative<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    type Key = MicKey<F, D::Key>;
+    type Description = GateDescription;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, mask) = *f;
+        if log_domain == 0 {
+            return Err("NonNegative(): Domain must contain at least two points".into());
+        }
+        let last = last_point(log_domain)?;
+        Mic::<F, D>::gen(&(log_domain, vec![(0, last / 2)], mask), rng)
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        Ok(Mic::<F, D>::eval(key, point)?[0])
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        Ok(*shares.0 - shares.1)
+    }
+}
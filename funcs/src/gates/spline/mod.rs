@@ -0,0 +1,183 @@
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, vec::Vec};
+
+use crate::{
+    gates::{add_mod, check_point, from_u128, last_point, share, sub_mod},
+    interval::{bcg21, bgi15, DIF},
+    AbelianGroup, Ring, FSS,
+};
+
+/// Spline gate built from the DIF of [[BGI15]], for polynomials with `C` coefficients.
+///
+/// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
+pub type Bgi15Spline<F, P, const C: usize> = Spline<F, bgi15::Bgi15DIF<[F; C], P>, C>;
+
+/// Spline gate built from the DIF of [[BCG+21]], for polynomials with `C` coefficients.
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21Spline<F, P, const C: usize> = Spline<F, bcg21::Bcg21DIF<[F; C], P>, C>;
+
+/// The description of a spline gate: the logarithm of the domain size, a list of pieces, and the
+/// input mask `r`. Each piece is given by the first point it contains and the `C` coefficients of
+/// its polynomial, from the constant term upwards. A piece contains every point up to the start
+/// of the next one, the first piece must start at 0, and the starts must be strictly increasing.
+pub type SplineDescription<F, const C: usize> = (usize, Vec<(usize, [F; C])>, usize);
+
+/// A spline gate, which outputs shares of a public piecewise-polynomial function on the masked
+/// input `x + r`.
+///
+/// Each polynomial is evaluated at the signed (two's complement) value of `x` in `Z_{2^n}`, so
+/// fixed-point encodings of negative numbers work directly. For fixed-point inputs with `s`
+/// fractional bits, scale the coefficient of degree `k` by `2^{-sk}` to evaluate the polynomial
+/// on the encoded value rather than the underlying integer.
+///
+/// Written as a function of the masked input, the coefficients of the polynomial being evaluated
+/// form a step function, which only changes at the start of each piece, at `2^{n - 1}` where the
+/// sign of `x` changes, and at the mask itself. Each step is secret-shared using a single key of
+/// the `DIF` `D`, whose range is the vector of all `C` coefficients, and the parties evaluate the
+/// polynomial with the shared coefficients at the public masked input.
+pub struct Spline<F, D, const C: usize>
+where
+    F: Ring,
+    [F; C]: AbelianGroup,
+    D: DIF<[F; C], Share = [F; C]>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+/// A key for a spline gate. A party's share of the coefficients of the polynomial being evaluated
+/// is `constants` minus the evaluation of `keys[i]` for each step `i`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SplineKey<F: Ring, K: Serialize + Deserialize, const C: usize> {
+    pub log_domain: usize,
+    pub keys: Vec<K>,
+    pub constants: [F; C],
+}
+
+impl<F, D, const C: usize> FSS for Spline<F, D, C>
+where
+    F: Ring,
+    [F; C]: AbelianGroup,
+    D: DIF<[F; C], Share = [F; C]>,
+{
+    type Key = SplineKey<F, D::Key, C>;
+    type Description = SplineDescription<F, C>;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, ref pieces, mask) = *f;
+        if log_domain == 0 {
+            return Err("Spline(): Domain must contain at least two points".into());
+        }
+        check_point(log_domain, mask)?;
+        if pieces.first().map(|(start, _)| *start) != Some(0) {
+            return Err("Spline(): The first piece must start at 0".into());
+        }
+        for (piece, next) in pieces.iter().zip(pieces.iter().skip(1)) {
+            if piece.0 >= next.0 {
+                return Err("Spline(): Pieces must have strictly increasing starts".into());
+            }
+        }
+        check_point(log_domain, pieces[pieces.len() - 1].0)?;
+
+        // The steps occur at the masked start of each piece and at the masked sign change. The
+        // mask is the masked start of the first piece, and a change at 0 is never needed.
+        let half = 1 << (log_domain - 1);
+        let mut steps: Vec<usize> = pieces.iter().map(|(start, _)| *start).collect();
+        if let Err(i) = steps.binary_search(&half) {
+            steps.insert(i, half);
+        }
+
+        // Share the coefficients at the end of the domain, and the change in the coefficients at
+        // each step
+        let (p1_constants, p2_constants) =
+            share(Self::coefficients(f, last_point(log_domain)?), rng);
+
+        let (mut p1_keys, mut p2_keys) = (Vec::new(), Vec::new());
+        for step in steps {
+            let masked_step = add_mod(log_domain, step, mask);
+            let before = Self::coefficients(f, sub_mod(log_domain, masked_step, 1));
+            let after = Self::coefficients(f, masked_step);
+            let (p1_key, p2_key) =
+                D::gen(&(log_domain, masked_step, after.group_sub(&before)), rng)?;
+            p1_keys.push(p1_key);
+            p2_keys.push(p2_key);
+        }
+
+        Ok((
+            SplineKey {
+                log_domain,
+                keys: p1_keys,
+                constants: p1_constants,
+            },
+            SplineKey {
+                log_domain,
+                keys: p2_keys,
+                constants: p2_constants,
+            },
+        ))
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        check_point(key.log_domain, *point)?;
+
+        // Compute the share of the coefficients, then evaluate the polynomial at the masked input
+        let mut coefficients = key.constants;
+        for step_key in &key.keys {
+            coefficients = coefficients.group_sub(&D::eval(step_key, point)?);
+        }
+
+        let masked = from_u128::<F>(*point as u128);
+        Ok(coefficients
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, c| acc * masked + c))
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        Ok(*shares.0 - shares.1)
+    }
+}
+
+impl<F, D, const C: usize> Spline<F, D, C>
+where
+    F: Ring,
+    [F; C]: AbelianGroup,
+    D: DIF<[F; C], Share = [F; C]>,
+{
+    /// Computes the coefficients of the polynomial evaluated on the masked input `masked`, as a
+    /// polynomial in the masked input.
+    fn coefficients(f: &SplineDescription<F, C>, masked: usize) -> [F; C] {
+        let (log_domain, ref pieces, mask) = *f;
+        let x = sub_mod(log_domain, masked, mask);
+        let piece = pieces.partition_point(|(start, _)| *start <= x) - 1;
+
+        // The signed value of `x` is `masked + delta`, where `delta` removes the mask and
+        // accounts for `x + r` wrapping around and for `x` being negative
+        let wrapped = masked < mask;
+        let negative = x >= 1 << (log_domain - 1);
+        let domain = from_u128::<F>(1 << log_domain);
+        let delta = match (wrapped, negative) {
+            (true, false) => domain,
+            (false, true) => -domain,
+            _ => F::zero(),
+        } - from_u128::<F>(mask as u128);
+
+        // Compute the coefficients of `p(masked + delta)` using a Taylor shift
+        let mut coefficients = pieces[piece].1;
+        for i in 0..C {
+            for j in (i..C.saturating_sub(1)).rev() {
+                let next = coefficients[j + 1];
+                coefficients[j] += delta * next;
+            }
+        }
+        coefficients
+    }
+}
@@ -0,0 +1,480 @@
+use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::test_rng;
+use rand::Rng;
+use rand_chacha::ChaChaRng;
+
+use crate::{
+    gates::{
+        equality, from_u128,
+        mic::{self, MicDescription},
+        offset::Offset,
+        relu,
+        spline::{self, SplineDescription},
+        truncation, GateDescription,
+    },
+    interval::two_sided,
+    point::bgi16,
+    test_field::F,
+    FixedKeyAes, Ring, Z2k, FSS,
+};
+
+// Set PRG type
+type PRG = ChaChaRng;
+
+// Aliases for various gate types
+type BGI15MIC = mic::Bgi15Mic<F, PRG>;
+type BCG21MIC = mic::Bcg21Mic<F, PRG>;
+type AesBCG21MIC = mic::Bcg21Mic<F, FixedKeyAes>;
+type BGI15Spline = spline::Bgi15Spline<F, PRG, 4>;
+type BCG21Spline = spline::Bcg21Spline<F, PRG, 4>;
+type BGI15Equality = equality::Bgi15Equality<F, PRG>;
+type BGI16Equality = equality::Bgi16Equality<F, PRG>;
+type AesBGI16Equality = equality::Bgi16Equality<F, FixedKeyAes>;
+type BGI16Offset = Offset<F, bgi16::Bgi16DPF<F, PRG>>;
+type TwoSidedBCG21Offset = Offset<F, two_sided::Bcg21TwoSidedDIF<F, PRG>>;
+type BCG21LogicalShift<R> = truncation::Bcg21LogicalShift<R, PRG>;
+type BCG21ArithmeticShift<R> = truncation::Bcg21ArithmeticShift<R, PRG>;
+type BCG21BitDecomposition<R> = truncation::Bcg21BitDecomposition<R, PRG>;
+type BCG21ReLU<R> = relu::Bcg21Relu<R, PRG>;
+type BCG21SignBit<R> = relu::Bcg21SignBit<R, PRG>;
+type BCG21NonNegative<R> = relu::Bcg21NonNegative<R, PRG>;
+
+fn test_mic_correctness_helper<G>()
+where
+    G: FSS<Description = MicDescription, Domain = usize, Range = Vec<F>>,
+{
+    let mut rng = test_rng();
+
+    for log_domain in (1usize..8).chain([64]) {
+        // Generate random intervals in the given domain, as well as the edge cases where an
+        // interval contains a single point, wraps around, or covers the entire domain
+        let last = last_point(log_domain);
+        let (p, q) = (rng.gen_range(0..=last), rng.gen_range(0..=last));
+        let mut intervals = vec![(p, q), (q, p), (p, p), (0, last), ((p + 1) & last, p)];
+        intervals.extend((0..4).map(|_| (rng.gen_range(0..=last), rng.gen_range(0..=last))));
+
+        for mask in [0, last, rng.gen_range(0..=last)] {
+            // Create the gate
+            let func = (log_domain, intervals.clone(), mask);
+            let (key1, key2) = G::gen(&func, &mut rng).unwrap();
+
+            // Evaluate every input on its masked value
+            for x in inputs(log_domain, &mut rng) {
+                let masked = x.wrapping_add(mask) & last;
+                let p1_result = G::eval(&key1, &masked).unwrap();
+                let p2_result = G::eval(&key2, &masked).unwrap();
+                let result = G::decode((&p1_result, &p2_result)).unwrap();
+
+                assert_eq!(result.len(), intervals.len());
+                for (&(p, q), y) in intervals.iter().zip(result) {
+                    let contained = match p <= q {
+                        true => p <= x && x <= q,
+                        false => x >= p || x <= q,
+                    };
+                    match contained {
+                        true => assert!(y == F::one()),
+                        false => assert!(y == F::zero()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn test_mic_bad_inputs_helper<G>()
+where
+    G: FSS<Description = MicDescription, Domain = usize, Range = Vec<F>, Share = Vec<F>>,
+{
+    let mut rng = test_rng();
+    let log_domain: usize = 10;
+    let max = 1 << log_domain;
+
+    // Intervals and masks outside the domain fail
+    assert!(G::gen(&(log_domain, vec![(0, max)], 0), &mut rng).is_err());
+    assert!(G::gen(&(log_domain, vec![(max, 0)], 0), &mut rng).is_err());
+    assert!(G::gen(&(log_domain, vec![(0, 1)], max), &mut rng).is_err());
+
+    // Masked inputs outside the domain fail, as do mismatched shares
+    let (k1, k2) = G::gen(&(log_domain, vec![(0, 1)], 1), &mut rng).unwrap();
+    assert!(G::eval(&k1, &max).is_err());
+    assert!(G::eval(&k2, &max).is_err());
+    assert!(G::decode((&vec![F::zero()], &vec![])).is_err());
+}
+
+/// Returns the signed (two's complement) value of `x` in a domain of size `2^log_domain`
+fn signed<R: Ring>(log_domain: usize, x: usize) -> R {
+    match x >= 1 << (log_domain - 1) {
+        true => -from_u128::<R>((1u128 << log_domain) - x as u128),
+        false => from_u128(x as u128),
+    }
+}
+
+/// Returns the last point of the domain of size `2^log_domain`
+fn last_point(log_domain: usize) -> usize {
+    usize::MAX >> (usize::BITS as usize - log_domain)
+}
+
+/// Returns the inputs to evaluate gates on: every point for small domains, and otherwise the ends
+/// of the domain and of each of its halves, along with random points
+fn inputs<R: Rng>(log_domain: usize, rng: &mut R) -> Vec<usize> {
+    let last = last_point(log_domain);
+    if log_domain < 8 {
+        return (0..=last).collect();
+    }
+    let half = 1 << (log_domain - 1);
+    let mut inputs = vec![0, 1, half - 1, half, last - 1, last];
+    inputs.extend((0..100).map(|_| rng.gen_range(0..=last)));
+    inputs
+}
+
+fn test_spline_correctness_helper<G>()
+where
+    G: FSS<Description = SplineDescription<F, 4>, Domain = usize, Range = F>,
+{
+    let mut rng = test_rng();
+
+    for log_domain in (1usize..8).chain([64]) {
+        // Generate random pieces with polynomials of different degrees, including a zero one
+        let last = last_point(log_domain);
+        let mut starts: Vec<usize> = (0..4).map(|_| rng.gen_range(0..=last)).collect();
+        starts.push(0);
+        starts.sort_unstable();
+        starts.dedup();
+        let pieces: Vec<(usize, [F; 4])> = starts
+            .iter()
+            .enumerate()
+            .map(|(degree, start)| {
+                let mut coefficients = [F::zero(); 4];
+                coefficients
+                    .iter_mut()
+                    .take(degree)
+                    .for_each(|c| *c = F::rand(&mut rng));
+                (*start, coefficients)
+            })
+            .collect();
+
+        // Evaluate the inputs on either side of the start of each piece
+        let mut inputs = inputs(log_domain, &mut rng);
+        inputs.extend(starts.iter().map(|start| start.wrapping_sub(1) & last));
+        inputs.extend(starts);
+
+        for mask in [0, last, rng.gen_range(0..=last)] {
+            // Create the gate
+            let func = (log_domain, pieces.clone(), mask);
+            let (key1, key2) = G::gen(&func, &mut rng).unwrap();
+
+            // Evaluate every input on its masked value, and compare against the polynomial of
+            // its piece evaluated at its signed value
+            for &x in &inputs {
+                let masked = x.wrapping_add(mask) & last;
+                let p1_result = G::eval(&key1, &masked).unwrap();
+                let p2_result = G::eval(&key2, &masked).unwrap();
+                let result = G::decode((&p1_result, &p2_result)).unwrap();
+
+                let piece = pieces.iter().rev().find(|(start, _)| *start <= x).unwrap();
+                let expected = piece
+                    .1
+                    .iter()
+                    .rev()
+                    .fold(F::zero(), |acc, c| acc * signed::<F>(log_domain, x) + c);
+                assert!(result == expected);
+            }
+        }
+    }
+}
+
+fn test_spline_bad_inputs_helper<G>()
+where
+    G: FSS<Description = SplineDescription<F, 4>, Domain = usize, Range = F>,
+{
+    let mut rng = test_rng();
+    let log_domain: usize = 10;
+    let max = 1 << log_domain;
+    let poly = [F::one(), F::zero(), F::zero(), F::zero()];
+
+    // Pieces which don't start at 0, aren't increasing, or leave the domain fail, as do masks
+    // outside the domain and empty domains
+    let bad_pieces = [
+        vec![],
+        vec![(1, poly)],
+        vec![(0, poly), (5, poly), (5, poly)],
+        vec![(0, poly), (7, poly), (3, poly)],
+        vec![(0, poly), (max, poly)],
+    ];
+    for pieces in bad_pieces {
+        assert!(G::gen(&(log_domain, pieces, 0), &mut rng).is_err());
+    }
+    assert!(G::gen(&(log_domain, vec![(0, poly)], max), &mut rng).is_err());
+    assert!(G::gen(&(0, vec![(0, poly)], 0), &mut rng).is_err());
+    assert!(G::gen(&(65, vec![(0, poly)], 0), &mut rng).is_err());
+
+    // Masked inputs outside the domain fail
+    let (k1, _) = G::gen(&(log_domain, vec![(0, poly)], 1), &mut rng).unwrap();
+    assert!(G::eval(&k1, &max).is_err());
+}
+
+fn test_gate_correctness_helper<R, G>(expected: impl Fn(usize, usize) -> R)
+where
+    R: Ring,
+    G: FSS<Description = GateDescription, Domain = usize, Range = R>,
+{
+    let mut rng = test_rng();
+
+    for log_domain in (1usize..8).chain([64]) {
+        let last = last_point(log_domain);
+        for mask in [0, last, rng.gen_range(0..=last)] {
+            // Create the gate
+            let (key1, key2) = G::gen(&(log_domain, mask), &mut rng).unwrap();
+
+            // Evaluate every input on its masked value
+            for x in inputs(log_domain, &mut rng) {
+                let masked = x.wrapping_add(mask) & last;
+                let p1_result = G::eval(&key1, &masked).unwrap();
+                let p2_result = G::eval(&key2, &masked).unwrap();
+                let result = G::decode((&p1_result, &p2_result)).unwrap();
+                assert!(result == expected(log_domain, x));
+            }
+
+            // Masked inputs outside the domain fail
+            if let Some(max) = last.checked_add(1) {
+                assert!(G::eval(&key1, &max).is_err());
+            }
+        }
+
+        // Masks outside the domain fail
+        if let Some(max) = last.checked_add(1) {
+            assert!(G::gen(&(log_domain, max), &mut rng).is_err());
+        }
+    }
+}
+
+#[test]
+fn test_mic_correctness() {
+    super::tests::test_mic_correctness_helper::<BGI15MIC>();
+    super::tests::test_mic_correctness_helper::<BCG21MIC>();
+    super::tests::test_mic_correctness_helper::<AesBCG21MIC>();
+}
+
+#[test]
+fn test_mic_bad_inputs() {
+    super::tests::test_mic_bad_inputs_helper::<BCG21MIC>();
+}
+
+#[test]
+fn test_mic_serialization() {
+    let mut rng = test_rng();
+    let log_domain: usize = 10;
+    let mask = rng.gen_range(0..(1 << log_domain));
+    let func = (log_domain, vec![(3, 100), (900, 7)], mask);
+    let (key1, _) = BCG21MIC::gen(&func, &mut rng).unwrap();
+
+    // A deserialized key evaluates identically
+    let mut serialized = vec![0; key1.serialized_size()];
+    key1.serialize(&mut serialized[..]).unwrap();
+    let recovered = <BCG21MIC as FSS>::Key::deserialize(serialized.as_slice()).unwrap();
+    for x in [0, 50, 901, (1 << log_domain) - 1] {
+        assert!(BCG21MIC::eval(&recovered, &x).unwrap() == BCG21MIC::eval(&key1, &x).unwrap());
+    }
+}
+
+#[test]
+fn test_spline_correctness() {
+    super::tests::test_spline_correctness_helper::<BGI15Spline>();
+    super::tests::test_spline_correctness_helper::<BCG21Spline>();
+}
+
+#[test]
+fn test_spline_bad_inputs() {
+    super::tests::test_spline_bad_inputs_helper::<BCG21Spline>();
+}
+
+fn test_relu_correctness_helper<R: Ring>() {
+    let negative = |log_domain: usize, x: usize| x >= 1 << (log_domain - 1);
+    super::tests::test_gate_correctness_helper::<R, BCG21ReLU<R>>(|log_domain, x| {
+        match negative(log_domain, x) {
+            true => R::zero(),
+            false => from_u128(x as u128),
+        }
+    });
+    super::tests::test_gate_correctness_helper::<R, BCG21SignBit<R>>(|log_domain, x| {
+        from_u128(negative(log_domain, x) as u128)
+    });
+    super::tests::test_gate_correctness_helper::<R, BCG21NonNegative<R>>(|log_domain, x| {
+        from_u128(!negative(log_domain, x) as u128)
+    });
+
+    // Domains with a single point have no sign
+    let mut rng = test_rng();
+    assert!(BCG21ReLU::<R>::gen(&(0, 0), &mut rng).is_err());
+    assert!(BCG21SignBit::<R>::gen(&(0, 0), &mut rng).is_err());
+    assert!(BCG21NonNegative::<R>::gen(&(0, 0), &mut rng).is_err());
+}
+
+fn test_truncation_correctness_helper<R: Ring>() {
+    let mut rng = test_rng();
+
+    for log_domain in (2usize..8).chain([64]) {
+        let last = last_point(log_domain);
+        let shifts: Vec<usize> = match log_domain < 8 {
+            true => (1..log_domain).collect(),
+            false => vec![1, 7, 32, log_domain - 1],
+        };
+        for shift in shifts {
+            for mask in [0, last, rng.gen_range(0..=last)] {
+                // Create the logical and arithmetic shift gates
+                let func = (log_domain, shift, mask);
+                let (p1_logical, p2_logical) =
+                    BCG21LogicalShift::<R>::gen(&func, &mut rng).unwrap();
+                let (p1_arith, p2_arith) = BCG21ArithmeticShift::<R>::gen(&func, &mut rng).unwrap();
+
+                // Evaluate every input on its masked value
+                for x in inputs(log_domain, &mut rng) {
+                    let masked = x.wrapping_add(mask) & last;
+                    let p1_result = BCG21LogicalShift::<R>::eval(&p1_logical, &masked).unwrap();
+                    let p2_result = BCG21LogicalShift::<R>::eval(&p2_logical, &masked).unwrap();
+                    let result = BCG21LogicalShift::<R>::decode((&p1_result, &p2_result)).unwrap();
+                    assert!(result == from_u128((x >> shift) as u128));
+
+                    let p1_result = BCG21ArithmeticShift::<R>::eval(&p1_arith, &masked).unwrap();
+                    let p2_result = BCG21ArithmeticShift::<R>::eval(&p2_arith, &masked).unwrap();
+                    let result =
+                        BCG21ArithmeticShift::<R>::decode((&p1_result, &p2_result)).unwrap();
+                    let signed = x as i128 - ((x > last / 2) as i128) * (last as i128 + 1);
+                    let expected = signed >> shift;
+                    match expected < 0 {
+                        true => assert!(result == -from_u128::<R>(expected.unsigned_abs())),
+                        false => assert!(result == from_u128(expected as u128)),
+                    }
+                }
+
+                // Masked inputs outside the domain fail
+                if let Some(max) = last.checked_add(1) {
+                    assert!(BCG21LogicalShift::<R>::eval(&p1_logical, &max).is_err());
+                    assert!(BCG21ArithmeticShift::<R>::eval(&p1_arith, &max).is_err());
+                }
+            }
+        }
+
+        // Shifts by nothing or the whole domain, and masks outside the domain fail
+        assert!(BCG21LogicalShift::<R>::gen(&(log_domain, 0, 0), &mut rng).is_err());
+        assert!(BCG21LogicalShift::<R>::gen(&(log_domain, log_domain, 0), &mut rng).is_err());
+        if let Some(max) = last.checked_add(1) {
+            assert!(BCG21LogicalShift::<R>::gen(&(log_domain, 1, max), &mut rng).is_err());
+        }
+    }
+}
+
+fn test_bit_decomposition_correctness_helper<R: Ring>() {
+    let mut rng = test_rng();
+
+    for log_domain in (1usize..8).chain([64]) {
+        let last = last_point(log_domain);
+        for mask in [0, last, rng.gen_range(0..=last)] {
+            // Create the gate
+            let func = (log_domain, mask);
+            let (key1, key2) = BCG21BitDecomposition::<R>::gen(&func, &mut rng).unwrap();
+
+            // Evaluate every input on its masked value
+            for x in inputs(log_domain, &mut rng) {
+                let masked = x.wrapping_add(mask) & last;
+                let p1_result = BCG21BitDecomposition::<R>::eval(&key1, &masked).unwrap();
+                let p2_result = BCG21BitDecomposition::<R>::eval(&key2, &masked).unwrap();
+                let result = BCG21BitDecomposition::<R>::decode((&p1_result, &p2_result)).unwrap();
+                assert_eq!(result.len(), log_domain);
+                for (i, bit) in result.into_iter().enumerate() {
+                    assert!(bit == from_u128(((x >> i) & 1) as u128));
+                }
+            }
+            if let Some(max) = last.checked_add(1) {
+                assert!(BCG21BitDecomposition::<R>::eval(&key1, &max).is_err());
+            }
+        }
+        if let Some(max) = last.checked_add(1) {
+            assert!(BCG21BitDecomposition::<R>::gen(&(log_domain, max), &mut rng).is_err());
+        }
+    }
+    assert!(BCG21BitDecomposition::<R>::gen(&(0, 0), &mut rng).is_err());
+}
+
+#[test]
+fn test_relu_correctness() {
+    super::tests::test_relu_correctness_helper::<F>();
+    super::tests::test_relu_correctness_helper::<Z2k<u64>>();
+}
+
+#[test]
+fn test_equality_correctness() {
+    let is_zero = |_, x| F::from(x == 0);
+    super::tests::test_gate_correctness_helper::<F, BGI15Equality>(is_zero);
+    super::tests::test_gate_correctness_helper::<F, BGI16Equality>(is_zero);
+    super::tests::test_gate_correctness_helper::<F, AesBGI16Equality>(is_zero);
+}
+
+#[test]
+fn test_offset_correctness() {
+    let mut rng = test_rng();
+
+    for log_domain in (1usize..8).chain([64]) {
+        let last = last_point(log_domain);
+        let (a, b) = (rng.gen_range(0..=last), rng.gen_range(0..=last));
+        let y = F::rand(&mut rng);
+        let output_mask = F::rand(&mut rng);
+
+        for input_mask in [0, last, rng.gen_range(0..=last)] {
+            // Create offset gates for a point function and a two-sided interval function
+            let func = ((log_domain, a, y), input_mask, output_mask);
+            let (p1_point, p2_point) = BGI16Offset::gen(&func, &mut rng).unwrap();
+            let func = ((log_domain, a, b, y), input_mask, output_mask);
+            let (p1_interval, p2_interval) = TwoSidedBCG21Offset::gen(&func, &mut rng).unwrap();
+
+            // Evaluating on each masked input gives the masked output
+            let mut inputs = inputs(log_domain, &mut rng);
+            inputs.extend([a, b]);
+            for x in inputs {
+                let masked = x.wrapping_add(input_mask) & last;
+                let p1_result = BGI16Offset::eval(&p1_point, &masked).unwrap();
+                let p2_result = BGI16Offset::eval(&p2_point, &masked).unwrap();
+                let result = BGI16Offset::decode((&p1_result, &p2_result)).unwrap();
+                match x == a {
+                    true => assert!(result == y + output_mask),
+                    false => assert!(result == output_mask),
+                }
+
+                let p1_result = TwoSidedBCG21Offset::eval(&p1_interval, &masked).unwrap();
+                let p2_result = TwoSidedBCG21Offset::eval(&p2_interval, &masked).unwrap();
+                let result = TwoSidedBCG21Offset::decode((&p1_result, &p2_result)).unwrap();
+                let contained = match a <= b {
+                    true => a <= x && x <= b,
+                    false => x >= a || x <= b,
+                };
+                match contained {
+                    true => assert!(result == y + output_mask),
+                    false => assert!(result == output_mask),
+                }
+            }
+        }
+
+        // Masks and functions outside the domain fail
+        if let Some(max) = last.checked_add(1) {
+            let func = ((log_domain, a, y), max, output_mask);
+            assert!(BGI16Offset::gen(&func, &mut rng).is_err());
+            let func = ((log_domain, max, y), 0, output_mask);
+            assert!(BGI16Offset::gen(&func, &mut rng).is_err());
+            let func = ((log_domain, a, max, y), 0, output_mask);
+            assert!(TwoSidedBCG21Offset::gen(&func, &mut rng).is_err());
+        }
+    }
+}
+
+#[test]
+fn test_truncation_correctness() {
+    super::tests::test_truncation_correctness_helper::<F>();
+    super::tests::test_truncation_correctness_helper::<Z2k<u64>>();
+}
+
+#[test]
+fn test_bit_decomposition_correctness() {
+    super::tests::test_bit_decomposition_correctness_helper::<F>();
+    super::tests::test_bit_decomposition_correctness_helper::<Z2k<u64>>();
+}
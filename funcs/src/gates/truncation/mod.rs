@@ -0,0 +1,288 @@
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, vec::Vec};
+
+use crate::{
+    gates::{add_mod, check_point, from_u128, last_point, share, GateDescription},
+    interval::{bcg21, DIF},
+    Ring, FSS,
+};
+
+/// Logical right shift gate built from the DIF of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21LogicalShift<F, P> = LogicalShift<F, bcg21::Bcg21DIF<F, P>>;
+
+/// Arithmetic right shift gate built from the DIF of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21ArithmeticShift<F, P> = ArithmeticShift<F, bcg21::Bcg21DIF<F, P>>;
+
+/// Bit decomposition gate built from the DIF of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21BitDecomposition<F, P> = BitDecomposition<F, bcg21::Bcg21DIF<F, P>>;
+
+/// The description of a right shift gate: the logarithm of the domain size, the number of bits
+/// `s` to shift by, and the input mask `r`. The shift must be between 1 and `n - 1`.
+pub type ShiftDescription = (usize, usize, usize);
+
+/// A key for a right shift gate. `wrap` and `low` are `DIF` keys for `[x + r < r]` and for the
+/// same comparison on the low `s` bits, and `high` and `one` are shares of `r >> s` and 1.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ShiftKey<F: Ring, K: Serialize + Deserialize> {
+    pub log_domain: usize,
+    pub shift: usize,
+    pub wrap: K,
+    pub low: K,
+    pub high: F,
+    pub one: F,
+}
+
+impl<F: Ring, K: Serialize + Deserialize> ShiftKey<F, K> {
+    /// Generates keys for a right shift gate using the `DIF` `D`
+    fn gen<D, RNG>(f: &ShiftDescription, rng: &mut RNG) -> Result<(Self, Self), Box<dyn Error>>
+    where
+        D: DIF<F, Key = K, Share = F>,
+        RNG: CryptoRng + RngCore,
+    {
+        let (log_domain, shift, mask) = *f;
+        check_point(log_domain, mask)?;
+        if shift == 0 || shift >= log_domain {
+            return Err("Shift(): Shift must be between 1 and log_domain - 1".into());
+        }
+
+        let (p1_wrap, p2_wrap) = D::gen(&(log_domain, mask, F::one()), rng)?;
+        let low_mask = mask & last_point(shift)?;
+        let (p1_low, p2_low) = D::gen(&(shift, low_mask, F::one()), rng)?;
+        let (p1_high, p2_high) = share(from_u128::<F>((mask >> shift) as u128), rng);
+        let (p1_one, p2_one) = share(F::one(), rng);
+
+        Ok((
+            ShiftKey {
+                log_domain,
+                shift,
+                wrap: p1_wrap,
+                low: p1_low,
+                high: p1_high,
+                one: p1_one,
+            },
+            ShiftKey {
+                log_domain,
+                shift,
+                wrap: p2_wrap,
+                low: p2_low,
+                high: p2_high,
+                one: p2_one,
+            },
+        ))
+    }
+
+    /// Evaluates the key on the masked input `point`, outputting shares of `x >> s`. Writing
+    /// `x + r` and `r` as their high and low `s` bits, this is
+    /// `(x + r)_h - r_h - [(x + r)_l < r_l] + 2^{n - s} [x + r < r]`.
+    fn eval<D>(&self, point: usize) -> Result<F, Box<dyn Error>>
+    where
+        D: DIF<F, Key = K, Share = F>,
+    {
+        check_point(self.log_domain, point)?;
+        let high = from_u128::<F>((point >> self.shift) as u128);
+        let low = D::eval(&self.low, &(point & last_point(self.shift)?))?;
+        let wrap = D::eval(&self.wrap, &point)?;
+        let scale = from_u128::<F>(1 << (self.log_domain - self.shift));
+        Ok(self.one * high - self.high - low + wrap * scale)
+    }
+}
+
+/// A logical right shift (truncation) gate, which outputs shares of `x >> s` on the masked input
+/// `x + r`, where `x` is interpreted as an unsigned value in `Z_{2^n}`.
+///
+/// This uses two `DIF` keys: one for whether adding the mask wraps around the domain, and one for
+/// whether adding the low `s` bits of the mask carries into the high bits.
+pub struct LogicalShift<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+impl<F, D> FSS for LogicalShift<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    type Key = ShiftKey<F, D::Key>;
+    type Description = ShiftDescription;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        ShiftKey::gen::<D, RNG>(f, rng)
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        key.eval::<D>(*point)
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        Ok(*shares.0 - shares.1)
+    }
+}
+
+/// An arithmetic right shift gate, which outputs shares of `x >> s` on the masked input `x + r`,
+/// where `x` is interpreted as a signed (two's complement) value in `Z_{2^n}`. Negative outputs
+/// are negative ring elements.
+///
+/// Adding `2^{n - 1}` to the input maps the signed inputs to unsigned inputs in the same order,
+/// so this is a logical right shift of `x + 2^{n - 1}`, minus `2^{n - s - 1}`. The masked input
+/// is shifted publicly, so the keys are the same as for a `LogicalShift`.
+pub struct ArithmeticShift<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+impl<F, D> FSS for ArithmeticShift<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    type Key = ShiftKey<F, D::Key>;
+    type Description = ShiftDescription;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        ShiftKey::gen::<D, RNG>(f, rng)
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        check_point(key.log_domain, *point)?;
+        let half = last_point(key.log_domain)? / 2 + 1;
+        let offset = add_mod(key.log_domain, *point, half);
+        let correction = from_u128::<F>(1 << (key.log_domain - key.shift - 1));
+        Ok(key.eval::<D>(offset)? - key.one * correction)
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        Ok(*shares.0 - shares.1)
+    }
+}
+
+/// A bit decomposition gate, which outputs shares of each bit of `x` on the masked input `x + r`,
+/// starting from the least significant bit.
+///
+/// Writing `c_j = [(x + r) mod 2^j < r mod 2^j]` for whether adding the low `j` bits of the mask
+/// carries, bit `i` of `x` is `(x + r)_i - r_i - c_i + 2 c_{i + 1}`. Each `c_j` uses a `DIF` key
+/// over the domain of the low `j` bits.
+pub struct BitDecomposition<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+/// A key for a bit decomposition gate. `carries[j - 1]` is the `DIF` key for `c_j`, and `bits`
+/// and `one` are shares of the bits of `r` and 1.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BitDecompositionKey<F: Ring, K: Serialize + Deserialize> {
+    pub log_domain: usize,
+    pub carries: Vec<K>,
+    pub bits: Vec<F>,
+    pub one: F,
+}
+
+impl<F, D> FSS for BitDecomposition<F, D>
+where
+    F: Ring,
+    D: DIF<F, Share = F>,
+{
+    type Key = BitDecompositionKey<F, D::Key>;
+    type Description = GateDescription;
+    type Domain = usize;
+    type Range = Vec<F>;
+    type Share = Vec<F>;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, mask) = *f;
+        if log_domain == 0 {
+            return Err("BitDecomposition(): Domain must contain at least two points".into());
+        }
+        check_point(log_domain, mask)?;
+
+        let (mut p1_carries, mut p2_carries) = (Vec::new(), Vec::new());
+        let (mut p1_bits, mut p2_bits) = (Vec::new(), Vec::new());
+        for j in 1..=log_domain {
+            let (p1_carry, p2_carry) = D::gen(&(j, mask & last_point(j)?, F::one()), rng)?;
+            p1_carries.push(p1_carry);
+            p2_carries.push(p2_carry);
+
+            let (p1_bit, p2_bit) = share(from_u128::<F>(((mask >> (j - 1)) & 1) as u128), rng);
+            p1_bits.push(p1_bit);
+            p2_bits.push(p2_bit);
+        }
+        let (p1_one, p2_one) = share(F::one(), rng);
+
+        Ok((
+            BitDecompositionKey {
+                log_domain,
+                carries: p1_carries,
+                bits: p1_bits,
+                one: p1_one,
+            },
+            BitDecompositionKey {
+                log_domain,
+                carries: p2_carries,
+                bits: p2_bits,
+                one: p2_one,
+            },
+        ))
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<Vec<F>, Box<dyn Error>> {
+        check_point(key.log_domain, *point)?;
+
+        // Evaluate every carry, with no carry into the least significant bit
+        let mut carries = vec![F::zero()];
+        for (j, carry) in key.carries.iter().enumerate() {
+            carries.push(D::eval(carry, &(point & last_point(j + 1)?))?);
+        }
+
+        Ok((0..key.log_domain)
+            .map(|i| {
+                let bit = from_u128::<F>(((point >> i) & 1) as u128);
+                key.one * bit - key.bits[i] - carries[i] + carries[i + 1] + carries[i + 1]
+            })
+            .collect())
+    }
+
+    fn decode(shares: (&Vec<F>, &Vec<F>)) -> Result<Vec<F>, Box<dyn Error>> {
+        if shares.0.len() != shares.1.len() {
+            return Err("Shares have different lengths".into());
+        }
+        Ok(shares
+            .0
+            .iter()
+            .zip(shares.1)
+            .map(|(s1, s2)| *s1 - s2)
+            .collect())
+    }
+}
@@ -0,0 +1,285 @@
+//! A module providing the abelian groups which tree-based FSS schemes output shares in
+use ark_ff::{
+    fields::{
+        CubicExtField, CubicExtParameters, Fp256, Fp256Parameters, Fp320, Fp320Parameters, Fp384,
+        Fp384Parameters, Fp448, Fp448Parameters, Fp64, Fp64Parameters, Fp768, Fp768Parameters,
+        Fp832, Fp832Parameters, QuadExtField, QuadExtParameters,
+    },
+    UniformRand, Zero,
+};
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::Rng;
+use std::fmt::Debug;
+
+/// An abelian group which FSS schemes can output shares in.
+///
+/// The group operation is written additively. Methods are prefixed with `group_` so that they
+/// don't clash with the arithmetic traits implemented by fields and rings.
+pub trait AbelianGroup:
+    'static + Copy + Debug + Eq + Send + Sync + Serialize + Deserialize
+{
+    /// Outputs the identity element
+    fn group_zero() -> Self;
+
+    /// Outputs the sum of `self` and `other`
+    fn group_add(&self, other: &Self) -> Self;
+
+    /// Outputs the inverse of `self`
+    fn group_neg(&self) -> Self;
+
+    /// Samples a uniformly random element using `rng`
+    fn group_sample<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    /// Outputs the difference of `self` and `other`
+    #[inline]
+    fn group_sub(&self, other: &Self) -> Self {
+        self.group_add(&other.group_neg())
+    }
+}
+
+/// Implements `AbelianGroup` for the additive group of an arkworks field
+macro_rules! impl_field_group {
+    ($($field:ident<$params:ident>),*) => {$(
+        impl<P: $params> AbelianGroup for $field<P> {
+            #[inline]
+            fn group_zero() -> Self {
+                Self::zero()
+            }
+
+            #[inline]
+            fn group_add(&self, other: &Self) -> Self {
+                *self + other
+            }
+
+            #[inline]
+            fn group_neg(&self) -> Self {
+                -*self
+            }
+
+            #[inline]
+            fn group_sample<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                Self::rand(rng)
+            }
+
+            #[inline]
+            fn group_sub(&self, other: &Self) -> Self {
+                *self - other
+            }
+        }
+    )*};
+}
+
+impl_field_group!(
+    Fp64<Fp64Parameters>,
+    Fp256<Fp256Parameters>,
+    Fp320<Fp320Parameters>,
+    Fp384<Fp384Parameters>,
+    Fp448<Fp448Parameters>,
+    Fp768<Fp768Parameters>,
+    Fp832<Fp832Parameters>,
+    QuadExtField<QuadExtParameters>,
+    CubicExtField<CubicExtParameters>
+);
+
+/// Implements `AbelianGroup` for the direct product of groups
+macro_rules! impl_tuple_group {
+    ($($name:ident: $idx:tt),+) => {
+        impl<$($name: AbelianGroup),+> AbelianGroup for ($($name,)+) {
+            #[inline]
+            fn group_zero() -> Self {
+                ($($name::group_zero(),)+)
+            }
+
+            #[inline]
+            fn group_add(&self, other: &Self) -> Self {
+                ($(self.$idx.group_add(&other.$idx),)+)
+            }
+
+            #[inline]
+            fn group_neg(&self) -> Self {
+                ($(self.$idx.group_neg(),)+)
+            }
+
+            #[inline]
+            fn group_sample<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                ($($name::group_sample(rng),)+)
+            }
+        }
+    };
+}
+
+impl_tuple_group!(A: 0, B: 1);
+impl_tuple_group!(A: 0, B: 1, C: 2);
+impl_tuple_group!(A: 0, B: 1, C: 2, D: 3);
+
+/// The direct product of `N` copies of a group
+impl<G: AbelianGroup, const N: usize> AbelianGroup for [G; N]
+where
+    [G; N]: Serialize + Deserialize,
+{
+    #[inline]
+    fn group_zero() -> Self {
+        [G::group_zero(); N]
+    }
+
+    #[inline]
+    fn group_add(&self, other: &Self) -> Self {
+        std::array::from_fn(|i| self[i].group_add(&other[i]))
+    }
+
+    #[inline]
+    fn group_neg(&self) -> Self {
+        self.map(|g| g.group_neg())
+    }
+
+    #[inline]
+    fn group_sample<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        std::array::from_fn(|_| G::group_sample(rng))
+    }
+}
+
+/// A group of bit strings under XOR, for schemes whose shares are combined by XOR rather than
+/// subtraction. Every element is its own inverse, so shares are decoded by XORing them.
+///
+/// This is implemented for `bool` and the unsigned integer types, and arrays of these give XOR
+/// groups of arbitrary byte strings, e.g. `[Xor<u8>; N]`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Xor<T>(pub T);
+
+impl AbelianGroup for Xor<bool> {
+    #[inline]
+    fn group_zero() -> Self {
+        Self(false)
+    }
+
+    #[inline]
+    fn group_add(&self, other: &Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+
+    #[inline]
+    fn group_neg(&self) -> Self {
+        *self
+    }
+
+    #[inline]
+    fn group_sample<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(rng.gen())
+    }
+}
+
+impl Serialize for Xor<bool> {
+    #[inline]
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.0.serialize(writer)
+    }
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        self.0.serialized_size()
+    }
+}
+
+impl Deserialize for Xor<bool> {
+    #[inline]
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(Self(bool::deserialize(reader)?))
+    }
+}
+
+macro_rules! impl_xor {
+    ($($t:ty),*) => {$(
+        impl AbelianGroup for Xor<$t> {
+            #[inline]
+            fn group_zero() -> Self {
+                Self(0)
+            }
+
+            #[inline]
+            fn group_add(&self, other: &Self) -> Self {
+                Self(self.0 ^ other.0)
+            }
+
+            #[inline]
+            fn group_neg(&self) -> Self {
+                *self
+            }
+
+            #[inline]
+            fn group_sample<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                Self(rng.gen())
+            }
+        }
+
+        impl Serialize for Xor<$t> {
+            #[inline]
+            fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+                Ok(writer.write_all(&self.0.to_le_bytes())?)
+            }
+
+            #[inline]
+            fn serialized_size(&self) -> usize {
+                std::mem::size_of::<$t>()
+            }
+        }
+
+        impl Deserialize for Xor<$t> {
+            #[inline]
+            fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+                let mut bytes = [0u8; std::mem::size_of::<$t>()];
+                reader.read_exact(&mut bytes)?;
+                Ok(Self(<$t>::from_le_bytes(bytes)))
+            }
+        }
+    )*};
+}
+
+impl_xor!(u8, u16, u32, u64, u128);
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+
+    use super::{AbelianGroup, Xor};
+    use crate::{test_field::F, Z2k};
+
+    fn test_group_helper<G: AbelianGroup>() {
+        let mut rng = test_rng();
+        let (a, b, c) = (
+            G::group_sample(&mut rng),
+            G::group_sample(&mut rng),
+            G::group_sample(&mut rng),
+        );
+
+        // Check the group axioms
+        assert_eq!(a.group_add(&G::group_zero()), a);
+        assert_eq!(a.group_add(&a.group_neg()), G::group_zero());
+        assert_eq!(a.group_add(&b), b.group_add(&a));
+        assert_eq!(a.group_add(&b).group_add(&c), a.group_add(&b.group_add(&c)));
+        assert_eq!(a.group_add(&b).group_sub(&b), a);
+
+        // Serialization round-trips
+        let mut serialized = vec![0; a.serialized_size()];
+        a.serialize(&mut serialized[..]).unwrap();
+        assert_eq!(G::deserialize(serialized.as_slice()).unwrap(), a);
+    }
+
+    #[test]
+    fn test_groups() {
+        test_group_helper::<F>();
+        test_group_helper::<Z2k<u64>>();
+        test_group_helper::<(F, Z2k<u8>)>();
+        test_group_helper::<(F, F, Z2k<u32>, Z2k<u128>)>();
+        test_group_helper::<[F; 4]>();
+        test_group_helper::<[(Z2k<u16>, F); 3]>();
+        test_group_helper::<[Z2k<u64>; 128]>();
+        test_group_helper::<Xor<bool>>();
+        test_group_helper::<Xor<u128>>();
+        test_group_helper::<[Xor<u8>; 32]>();
+
+        // XOR groups are their own inverse
+        let a = Xor(0b1010u8);
+        assert_eq!(a.group_neg(), a);
+        assert_eq!(a.group_sub(&Xor(0b0110)), Xor(0b1100));
+    }
+}
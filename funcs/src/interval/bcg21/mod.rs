@@ -0,0 +1,223 @@
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, vec::Vec};
+
+use crate::{
+    interval::{
+        bgi15::{self, Node},
+        DIF,
+    },
+    point::bgi15::IntermediateNode,
+    tree::{TreeFSS, TreeScheme},
+    AbelianGroup, Expansion, Pair, Seed, TreeDomain, TreePrg,
+};
+
+/// DIF scheme based on the distributed comparison function of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21DIF<F, P, D = usize> = TreeScheme<F, P, Bcg21<F, P, D>>;
+
+impl<F, P, D> DIF<F, D> for Bcg21DIF<F, P, D>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+}
+
+/// A `CodeWord` corrects the children of a node whose control-bit is set. It contains a single
+/// seed and group element, which are applied to whichever child is being evaluated, and a
+/// control-bit for each child.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CodeWord<F: AbelianGroup, S: Seed> {
+    pub seed: S,
+    pub control_bits: Pair<bool>,
+    pub elem: F,
+}
+
+pub struct Bcg21<F, P, D = usize>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+    _field: PhantomData<F>,
+    _prg: PhantomData<P>,
+    _domain: PhantomData<D>,
+}
+
+impl<F, P, D> TreeFSS<F, P> for Bcg21<F, P, D>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+    type Root = Node<F, P::Seed>;
+    type Codeword = CodeWord<F, P::Seed>;
+    type Description = super::IFDescription<F, D>;
+    type Domain = D;
+    type Node = Node<F, P::Seed>;
+    type EvaluationNode = IntermediateNode<P::Seed>;
+
+    fn get_domain_and_point(f: &Self::Description) -> Result<(usize, Vec<bool>), Box<dyn Error>> {
+        bgi15::Bgi15::<F, P, D>::get_domain_and_point(f)
+    }
+
+    fn gen_root<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        bit: bool,
+        rng: &mut RNG,
+    ) -> (Self::Root, Self::Root) {
+        // The root nodes of [BGI15] already satisfy the invariant needed here: the parties'
+        // children along `bit` have independent seeds, different control-bits and accumulators
+        // which cancel, and their children along `!bit` have identical seeds and control-bits
+        // and accumulators which differ by `val` exactly when `!bit` is the left child.
+        bgi15::Bgi15::<F, P, D>::gen_root(f, bit, rng)
+    }
+
+    fn evaluate_root(bit: bool, root: &Self::Root) -> (Self::EvaluationNode, Option<F>) {
+        bgi15::Bgi15::<F, P, D>::evaluate_root(bit, root)
+    }
+
+    fn seed(node: &Self::EvaluationNode) -> &P::Seed {
+        bgi15::Bgi15::<F, P, D>::seed(node)
+    }
+
+    fn sample_masked_level(node: &Self::EvaluationNode, expansion: Expansion<P>) -> Self::Node {
+        bgi15::Bgi15::<F, P, D>::sample_masked_level(node, expansion)
+    }
+
+    fn compute_codeword<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        bit: bool,
+        p1_node: &Self::EvaluationNode,
+        p1_masked_node: &Self::Node,
+        p2_masked_node: &Self::Node,
+        accumulated: Option<F>,
+        _: &mut RNG,
+    ) -> Self::Codeword {
+        let (_, _, val) = *f;
+        let accumulated = accumulated.unwrap_or_else(F::group_zero);
+
+        // As in [BGI16], the seed mask is the XOR of the parties' seeds corresponding to
+        // `!point`, and the control-bit masks ensure that the parties' control-bits are
+        // different for `point` and the same for `!point`.
+        let mut codeword_seed = P::Seed::default();
+        codeword_seed
+            .as_mut()
+            .iter_mut()
+            .zip(p1_masked_node.seeds[!bit].as_ref())
+            .zip(p2_masked_node.seeds[!bit].as_ref())
+            .for_each(|((cs, p1_s), p2_s)| {
+                *cs = p1_s ^ p2_s;
+            });
+
+        let mut codeword_control_bits = Pair::<bool>::default();
+        codeword_control_bits[bit] =
+            true ^ p1_masked_node.control_bits[bit] ^ p2_masked_node.control_bits[bit];
+        codeword_control_bits[!bit] =
+            false ^ p1_masked_node.control_bits[!bit] ^ p2_masked_node.control_bits[!bit];
+
+        // The parties hold identical nodes below `!point`, so the difference of their
+        // accumulators is fixed once they leave the path. The group element mask corrects this
+        // difference to `val` when leaving to the left, i.e. for inputs less than `point`, and to
+        // 0 otherwise. The mask is also added to the `point` child, and `gen` tracks the
+        // resulting difference along the path.
+        let target = match bit {
+            true => val,
+            false => F::group_zero(),
+        };
+        let elem = target
+            .group_sub(&accumulated)
+            .group_sub(&p1_masked_node.elems[!bit])
+            .group_add(&p2_masked_node.elems[!bit]);
+
+        CodeWord {
+            seed: codeword_seed,
+            control_bits: codeword_control_bits,
+            elem: match p1_node.control_bit {
+                true => elem,
+                false => elem.group_neg(),
+            },
+        }
+    }
+
+    fn compute_next_level(
+        bit: bool,
+        node: &Self::EvaluationNode,
+        masked_node: Self::Node,
+        codeword: &Self::Codeword,
+        accumulator: Option<&mut F>,
+    ) -> Self::EvaluationNode {
+        let mut next = IntermediateNode::new(bit, &masked_node.seeds, &masked_node.control_bits);
+        let mut elem = masked_node.elems[bit];
+
+        // Only apply the codeword if the current control-bit is set
+        if node.control_bit {
+            next.seed
+                .as_mut()
+                .iter_mut()
+                .zip(codeword.seed.as_ref())
+                .for_each(|(s, cs)| *s ^= cs);
+            next.control_bit ^= codeword.control_bits[bit];
+            elem = elem.group_add(&codeword.elem);
+        }
+
+        if let Some(acc) = accumulator {
+            *acc = acc.group_add(&elem);
+        }
+        next
+    }
+
+    #[inline]
+    fn compute_mask(
+        _: &Self::Description,
+        p1_node: &Self::EvaluationNode,
+        p2_node: &Self::EvaluationNode,
+        accumulated: Option<F>,
+    ) -> Result<Option<F>, Box<dyn Error>> {
+        let accumulated = accumulated.ok_or("Gen(): Scheme has no accumulator")?;
+        let p1_elem = Self::output_elem(p1_node);
+        let p2_elem = Self::output_elem(p2_node);
+
+        // The input equal to `point` isn't less than it, so output a mask s.t. the party whose
+        // final control-bit is set can correct the parties' outputs to cancel
+        let mask = accumulated
+            .group_neg()
+            .group_sub(&p1_elem)
+            .group_add(&p2_elem);
+        match p2_node.control_bit {
+            true => Ok(Some(mask.group_neg())),
+            false => Ok(Some(mask)),
+        }
+    }
+
+    #[inline]
+    fn compute_output(
+        node: &Self::EvaluationNode,
+        mask: Option<&F>,
+        accumulator: Option<F>,
+    ) -> Result<F, Box<dyn Error>> {
+        let mask = mask.ok_or("Eval(): Key has no output mask")?;
+        let accumulator = accumulator.ok_or("Eval(): Scheme has no accumulator")?;
+        match node.control_bit {
+            true => Ok(accumulator
+                .group_add(&Self::output_elem(node))
+                .group_add(mask)),
+            false => Ok(accumulator.group_add(&Self::output_elem(node))),
+        }
+    }
+}
+
+impl<F, P, D> Bcg21<F, P, D>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+    /// Using the PRG seed of a leaf, sample a random group element
+    #[inline]
+    fn output_elem(node: &IntermediateNode<P::Seed>) -> F {
+        F::group_sample(&mut P::material(&node.seed))
+    }
+}
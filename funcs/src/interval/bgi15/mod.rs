@@ -1,32 +1,31 @@
-use ark_ff::Field;
 use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
-use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use rand::{CryptoRng, Rng, RngCore};
 use std::{error::Error, marker::PhantomData, vec::Vec};
 
 use crate::{
     interval::DIF,
     point::bgi15::IntermediateNode,
     tree::{TreeFSS, TreeScheme},
-    Pair, Seed,
+    AbelianGroup, Expansion, Pair, Seed, TreeDomain, TreePrg,
 };
 
 /// DIF scheme based on [[BGI15]].
 ///
 /// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
-pub type Bgi15DIF<F, PRG, S> = TreeScheme<F, PRG, S, Bgi15<F, PRG, S>>;
+pub type Bgi15DIF<F, P, D = usize> = TreeScheme<F, P, Bgi15<F, P, D>>;
 
-impl<F, PRG, S> DIF<F> for Bgi15DIF<F, PRG, S>
+impl<F, P, D> DIF<F, D> for Bgi15DIF<F, P, D>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
 {
 }
 
-/// A node in the DIF tree is composed of a seed, control-bit, and field element corresponding to
+/// A node in the DIF tree is composed of a seed, control-bit, and group element corresponding to
 /// each child node
 #[derive(Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
-pub struct Node<F: Field, S: Seed> {
+pub struct Node<F: AbelianGroup, S: Seed> {
     pub seeds: Pair<S>,
     pub control_bits: Pair<bool>,
     pub elems: Pair<F>,
@@ -36,32 +35,33 @@ pub struct Node<F: Field, S: Seed> {
 /// seed/control-bit values.
 pub type CodeWord<F, S> = Node<F, S>;
 
-pub struct Bgi15<F, PRG, S>
+pub struct Bgi15<F, P, D = usize>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
 {
     _field: PhantomData<F>,
-    _prg: PhantomData<PRG>,
-    _seed: PhantomData<S>,
+    _prg: PhantomData<P>,
+    _domain: PhantomData<D>,
 }
 
-impl<F, PRG, S> TreeFSS<F, PRG, S> for Bgi15<F, PRG, S>
+impl<F, P, D> TreeFSS<F, P> for Bgi15<F, P, D>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
 {
-    type Root = Node<F, S>;
-    type Codeword = Pair<CodeWord<F, S>>;
-    type Description = super::IFDescription<F>;
-    type Node = Node<F, S>;
-    type EvaluationNode = IntermediateNode<S>;
+    type Root = Node<F, P::Seed>;
+    type Codeword = Pair<CodeWord<F, P::Seed>>;
+    type Description = super::IFDescription<F, D>;
+    type Domain = D;
+    type Node = Node<F, P::Seed>;
+    type EvaluationNode = IntermediateNode<P::Seed>;
 
     fn get_domain_and_point(f: &Self::Description) -> Result<(usize, Vec<bool>), Box<dyn Error>> {
         let log_domain = f.0;
-        let x = crate::usize_to_bits(log_domain, f.1)?;
+        let x = f.1.to_bits(log_domain)?;
         Ok((log_domain, x))
     }
 
@@ -72,8 +72,8 @@ where
     ) -> (Self::Root, Self::Root) {
         let (_, _, val) = *f;
 
-        // Sample party 1's initial 0/1 seeds, control bits, and field elements.
-        let mut p1_seeds = Pair::<S>::default();
+        // Sample party 1's initial 0/1 seeds, control bits, and group elements.
+        let mut p1_seeds = Pair::<P::Seed>::default();
         rng.fill_bytes(p1_seeds[0].as_mut());
         rng.fill_bytes(p1_seeds[1].as_mut());
 
@@ -81,16 +81,16 @@ where
         p1_control_bits[0] = rng.gen_bool(0.5);
         p1_control_bits[1] = rng.gen_bool(0.5);
 
-        let mut p1_elems = Pair::<F>::default();
-        p1_elems[0] = F::rand(rng);
-        p1_elems[1] = F::rand(rng);
+        let mut p1_elems = Pair::new(F::group_zero(), F::group_zero());
+        p1_elems[0] = F::group_sample(rng);
+        p1_elems[1] = F::group_sample(rng);
 
-        // Sample party 2's initial seeds, control bits, and field elements. The seed for the bit
+        // Sample party 2's initial seeds, control bits, and group elements. The seed for the bit
         // corresponding to `!bit` will be the same as party 1, the control bit for `bit` will be
         // different from party 1, the control bit for `!bit` will be the same as party 1, the
-        // field element for the bit corresponding to `bit` will be secret shares of 0, and the
-        // field elements for `!bit` will be secret shares of `val * bit`.
-        let mut p2_seeds = Pair::<S>::default();
+        // group element for the bit corresponding to `bit` will be secret shares of 0, and the
+        // group elements for `!bit` will be secret shares of `val * bit`.
+        let mut p2_seeds = Pair::<P::Seed>::default();
         rng.fill_bytes(p2_seeds[bit].as_mut());
         p2_seeds[!bit] = p1_seeds[!bit];
 
@@ -98,10 +98,10 @@ where
         p2_control_bits[bit] = !p1_control_bits[bit];
         p2_control_bits[!bit] = p1_control_bits[!bit];
 
-        let mut p2_elems = Pair::<F>::default();
+        let mut p2_elems = Pair::new(F::group_zero(), F::group_zero());
         p2_elems[bit] = p1_elems[bit];
         p2_elems[!bit] = match bit {
-            true => p1_elems[!bit] - val,
+            true => p1_elems[!bit].group_sub(&val),
             false => p1_elems[!bit],
         };
 
@@ -128,23 +128,18 @@ where
         )
     }
 
-    fn sample_masked_level(node: &Self::EvaluationNode) -> Self::Node {
-        let mut prg = PRG::from_seed(node.seed);
-
-        // Sample masked seeds.
-        let mut masked_seeds = Pair::<S>::default();
-        prg.fill_bytes(masked_seeds[0].as_mut());
-        prg.fill_bytes(masked_seeds[1].as_mut());
+    fn seed(node: &Self::EvaluationNode) -> &P::Seed {
+        &node.seed
+    }
 
-        // Sample masked control-bits
-        let mut masked_control_bits = Pair::<bool>::default();
-        masked_control_bits[0] = prg.gen_bool(0.5);
-        masked_control_bits[1] = prg.gen_bool(0.5);
+    fn sample_masked_level(_: &Self::EvaluationNode, expansion: Expansion<P>) -> Self::Node {
+        // The expansion of the seed holds the masked seeds and control-bits
+        let (masked_seeds, masked_control_bits, mut material) = expansion;
 
-        // Sample masked field elems
-        let mut masked_elems = Pair::<F>::default();
-        masked_elems[0] = F::rand(&mut prg);
-        masked_elems[1] = F::rand(&mut prg);
+        // Sample masked group elems
+        let mut masked_elems = Pair::new(F::group_zero(), F::group_zero());
+        masked_elems[0] = F::group_sample(&mut material);
+        masked_elems[1] = F::group_sample(&mut material);
 
         Self::Node {
             seeds: masked_seeds,
@@ -159,6 +154,7 @@ where
         p1_node: &Self::EvaluationNode,
         p1_masked_node: &Self::Node,
         p2_masked_node: &Self::Node,
+        _: Option<F>,
         rng: &mut RNG,
     ) -> Self::Codeword {
         let (_, _, val) = *f;
@@ -168,15 +164,15 @@ where
         // `MaskedNode` in order to get the next `Node`.
         //
         // These masks are designed such that, if the parties are evaluating the path
-        // at or to the right of `point`, then the difference of the resulting field elements
+        // at or to the right of `point`, then the difference of the resulting group elements
         // will be zero. However, if the path is ever to the left of `point` then the
-        // difference of the resulting field elements will be `val`.
-        let mut codeword_0_seeds = Pair::<S>::default();
+        // difference of the resulting group elements will be `val`.
+        let mut codeword_0_seeds = Pair::<P::Seed>::default();
         let mut codeword_0_control_bits = Pair::<bool>::default();
-        let mut codeword_0_elems = Pair::<F>::default();
-        let mut codeword_1_seeds = Pair::<S>::default();
+        let mut codeword_0_elems = Pair::new(F::group_zero(), F::group_zero());
+        let mut codeword_1_seeds = Pair::<P::Seed>::default();
         let mut codeword_1_control_bits = Pair::<bool>::default();
-        let mut codeword_1_elems = Pair::<F>::default();
+        let mut codeword_1_elems = Pair::new(F::group_zero(), F::group_zero());
 
         // The seed masks corresponding to `point` are sampled randomly
         rng.fill_bytes(codeword_0_seeds[bit].as_mut());
@@ -216,44 +212,38 @@ where
             ^ p1_masked_node.control_bits[!bit]
             ^ p2_masked_node.control_bits[!bit];
 
-        // The field-elements corresponding to `point` are sampled randomly according to the
-        // following constraint: the difference of the parties' field elements is equal to
+        // The group elements corresponding to `point` are sampled randomly according to the
+        // following constraint: the difference of the parties' group elements is equal to
         // zero
         //
         // Note that the match statement is necessary since this is a subtractive FSS so the
         // signs of things may change depending on which party selects which codeword.
-        codeword_0_elems[bit] = F::rand(rng);
+        codeword_0_elems[bit] = F::group_sample(rng);
+        let diff = p1_masked_node.elems[bit].group_sub(&p2_masked_node.elems[bit]);
         codeword_1_elems[bit] = match p1_node.control_bit {
-            true => {
-                F::zero() + codeword_0_elems[bit] - p1_masked_node.elems[bit]
-                    + p2_masked_node.elems[bit]
-            }
-            false => {
-                F::zero() + codeword_0_elems[bit] + p1_masked_node.elems[bit]
-                    - p2_masked_node.elems[bit]
-            }
+            true => codeword_0_elems[bit].group_sub(&diff),
+            false => codeword_0_elems[bit].group_add(&diff),
         };
 
-        // The field-elements corresponding to `!point` are sampled randomly according to the
-        // following constraint: the difference of the parties' field elements is equal to `val
+        // The group elements corresponding to `!point` are sampled randomly according to the
+        // following constraint: the difference of the parties' group elements is equal to `val
         // * bit`
         //
         // Note that the match statement is necessary since this is a subtractive FSS so the
         // signs of things may change depending on which party selects which codeword.
-        codeword_0_elems[!bit] = F::rand(rng);
+        codeword_0_elems[!bit] = F::group_sample(rng);
         // `g := bit * val`
         let g = match bit {
             true => val,
-            false => F::zero(),
+            false => F::group_zero(),
         };
+        let diff = p1_masked_node.elems[!bit].group_sub(&p2_masked_node.elems[!bit]);
         codeword_1_elems[!bit] = match p1_node.control_bit {
-            true => {
-                g + codeword_0_elems[!bit] - p1_masked_node.elems[!bit] + p2_masked_node.elems[!bit]
-            }
-            false => {
-                -g + codeword_0_elems[!bit] + p1_masked_node.elems[!bit]
-                    - p2_masked_node.elems[!bit]
-            }
+            true => g.group_add(&codeword_0_elems[!bit]).group_sub(&diff),
+            false => g
+                .group_neg()
+                .group_add(&codeword_0_elems[!bit])
+                .group_add(&diff),
         };
 
         // Using the masked nodes and generated codewords, derive the node for the next level
@@ -292,7 +282,9 @@ where
 
         // If an accumulator is provided, update it
         if let Some(acc) = accumulator {
-            *acc += masked_node.elems[bit] + codeword.elems[bit];
+            *acc = acc
+                .group_add(&masked_node.elems[bit])
+                .group_add(&codeword.elems[bit]);
         }
         IntermediateNode {
             seed: masked_node.seeds[bit],
@@ -300,17 +292,23 @@ where
         }
     }
 
-    #[inline]
-    fn compute_output_elem(_: &Self::EvaluationNode) -> Option<F> {
-        None
-    }
-
     #[inline]
     fn compute_mask(
         _: &Self::Description,
-        _: &Option<F>,
-        _: &Option<F>,
+        _: &Self::EvaluationNode,
+        _: &Self::EvaluationNode,
+        _: Option<F>,
     ) -> Result<Option<F>, Box<dyn Error>> {
         Ok(None)
     }
+
+    #[inline]
+    fn compute_output(
+        _: &Self::EvaluationNode,
+        _: Option<&F>,
+        accumulator: Option<F>,
+    ) -> Result<F, Box<dyn Error>> {
+        // The accumulated group elements along the path are the output share
+        Ok(accumulator.ok_or("Eval(): Accumulator is None")?)
+    }
 }
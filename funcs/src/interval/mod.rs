@@ -1,7 +1,6 @@
 //! A module implementing various distributed interval function schemes
-use ark_ff::Field;
 
-use crate::FSS;
+use crate::{AbelianGroup, FSS};
 
 #[cfg(test)]
 pub(crate) mod tests;
@@ -11,7 +10,18 @@ pub(crate) mod tests;
 /// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
 pub mod bgi15;
 
-/// The domain of an interval function
+/// DIF scheme based on the distributed comparison function of [[BCG+21]], which uses a single seed
+/// and field element correction per level.
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub mod bcg21;
+
+/// Two-sided DIF schemes built from a pair of one-sided DIF keys.
+pub mod two_sided;
+
+/// The default domain of an interval function. Schemes can also be defined over other
+/// `TreeDomain`s, e.g. `u128` or `BitString`, whose points are compared by their bit
+/// decomposition.
 type IFDomain = usize;
 
 /// The range of an interval function
@@ -20,10 +30,23 @@ type IFRange<F> = F;
 /// The description of an interval function: the logarithm of the domain size, a
 /// point `x` in that domain, and the evalutaion value of any point `y` where
 /// `y < x`
-type IFDescription<F> = (usize, usize, F);
+pub(crate) type IFDescription<F, D = IFDomain> = (usize, D, F);
+
+/// A distributed interval function (DIF) is a type of FSS scheme for interval functions over the
+/// domain `D`.
+pub trait DIF<F: AbelianGroup, D = IFDomain>:
+    FSS<Domain = D, Range = IFRange<F>, Description = IFDescription<F, D>>
+{
+}
+
+/// The description of a two-sided interval function: the logarithm of the domain size, the
+/// endpoints `a` and `b` of the interval in that domain, and the evaluation value of any point
+/// `x` where `a <= x <= b`. When `a > b` the interval wraps around the end of the domain, i.e.
+/// it contains any `x` where `x >= a` or `x <= b`.
+pub(crate) type TwoSidedIFDescription<F> = (usize, usize, usize, F);
 
-/// A distributed interval function (DIF) is a type of FSS scheme for interval functions.
-pub trait DIF<F: Field>:
-    FSS<Domain = IFDomain, Range = IFRange<F>, Description = IFDescription<F>>
+/// A two-sided DIF is a type of FSS scheme for interval functions bounded on both sides.
+pub trait TwoSidedDIF<F: AbelianGroup>:
+    FSS<Domain = IFDomain, Range = IFRange<F>, Description = TwoSidedIFDescription<F>>
 {
 }
@@ -1,54 +1,53 @@
-use ark_ff::{
-    BigInteger64 as BigInteger, FftParameters, Fp64, Fp64Parameters, FpParameters, One,
-    UniformRand, Zero,
-};
+use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::test_rng;
 use rand::Rng;
 use rand_chacha::ChaChaRng;
 
-use crate::interval::{bgi15, DIF};
+use crate::{
+    interval::{bcg21, bgi15, two_sided, IFDescription, TwoSidedDIF, DIF},
+    test_field::F,
+    tree::{TreeFSS, TreeKey, TreeScheme},
+    AbelianGroup, BitString, FixedKeyAes, TreePrg, Z2k, FSS,
+};
 
-// Set field, seed, and PRG types
-type F = Fp64<FParameters>;
-type S = [u8; 32];
+// Set PRG type
 type PRG = ChaChaRng;
 
-// Aliases for various DPF types
-type BGI15 = bgi15::Bgi15DIF<F, PRG, S>;
+// Aliases for various DIF types
+type BGI15 = bgi15::Bgi15DIF<F, PRG>;
+type AesBGI15 = bgi15::Bgi15DIF<F, FixedKeyAes>;
+type BCG21 = bcg21::Bcg21DIF<F, PRG>;
+type AesBCG21 = bcg21::Bcg21DIF<F, FixedKeyAes>;
+type TwoSidedBGI15 = two_sided::Bgi15TwoSidedDIF<F, PRG>;
+type TwoSidedBCG21 = two_sided::Bcg21TwoSidedDIF<F, PRG>;
 
-// Define a field to use. This is the same 63-bit field used in
-// "Lightweight Techniques for Private Heavy Hitters"
-struct FParameters;
+// Aliases for DIF types over the ring `Z_{2^64}`
+type R = Z2k<u64>;
+type RingBGI15 = bgi15::Bgi15DIF<R, PRG>;
+type RingBCG21 = bcg21::Bcg21DIF<R, PRG>;
+type RingTwoSidedBCG21 = two_sided::Bcg21TwoSidedDIF<R, PRG>;
 
-impl Fp64Parameters for FParameters {}
-impl FftParameters for FParameters {
-    type BigInt = BigInteger;
-    const TWO_ADICITY: u32 = 1;
-    const TWO_ADIC_ROOT_OF_UNITY: Self::BigInt = BigInteger([1]);
-}
+// Aliases for DIF types over products of groups
+type G = (F, Z2k<u32>);
+type GroupBGI15 = bgi15::Bgi15DIF<G, PRG>;
+type ArrayBGI15 = bgi15::Bgi15DIF<[F; 4], PRG>;
+type ArrayTwoSidedBGI15 = two_sided::Bgi15TwoSidedDIF<[F; 4], PRG>;
 
-impl FpParameters for FParameters {
-    const MODULUS: BigInteger = BigInteger([9223372036854775783]);
-    const MODULUS_BITS: u32 = 63u32;
-    const REPR_SHAVE_BITS: u32 = 1;
-    const R: BigInteger = BigInteger([50]);
-    const R2: BigInteger = BigInteger([2500]);
-    const INV: u64 = 1106804644422573097;
-    const GENERATOR: BigInteger = BigInteger([3]);
-    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
-    const T: BigInteger = BigInteger([4611686018427387891]);
-    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([2305843009213693945]);
-    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([4611686018427387891]);
-}
+// Aliases for DIF types over domains larger than `usize`
+type LargeBGI15 = bgi15::Bgi15DIF<F, PRG, u128>;
+type LargeBCG21 = bcg21::Bcg21DIF<F, PRG, u128>;
+type BitStringBGI15 = bgi15::Bgi15DIF<F, PRG, BitString>;
+type BitStringBCG21 = bcg21::Bcg21DIF<F, PRG, BitString>;
 
-fn test_correctness_helper<D: DIF<F>>() {
+fn test_correctness_helper<G: AbelianGroup, D: DIF<G>>() {
     let mut rng = test_rng();
 
     for log_domain in 2usize..12 {
         // Generate a random point in the given domain and field value
         let valid_range = 0..(1 << log_domain);
         let x = rng.gen_range(valid_range.clone());
-        let y = F::rand(&mut rng);
+        let y = G::group_sample(&mut rng);
 
         // Create the DIF
         let func = (log_domain, x, y);
@@ -56,6 +55,34 @@ fn test_correctness_helper<D: DIF<F>>() {
 
         // Evaluate each point of the DIF
         for p in valid_range.clone() {
+            let p1_result = D::eval(&key1, &p).unwrap();
+            let p2_result = D::eval(&key2, &p).unwrap();
+            if p < x {
+                assert!(D::decode((&p1_result, &p2_result)).unwrap() == y)
+            } else {
+                assert!(D::decode((&p1_result, &p2_result)).unwrap() == G::group_zero())
+            }
+        }
+    }
+}
+
+fn test_large_domain_helper<D: DIF<F, u128>>() {
+    let mut rng = test_rng();
+
+    for log_domain in [64usize, 100, 128] {
+        // Generate a random point in the given domain and field value
+        let max = u128::MAX >> (128 - log_domain);
+        let x = rng.gen::<u128>() & max;
+        let y = F::rand(&mut rng);
+
+        // Create the DIF
+        let func = (log_domain, x, y);
+        let (key1, key2) = D::gen(&func, &mut rng).unwrap();
+
+        // Evaluate the DIF at the ends of the domain, around the point, and at random points
+        let mut points = vec![0, max, x, x.saturating_sub(1), x.saturating_add(1) & max];
+        points.extend((0..20).map(|_| rng.gen::<u128>() & max));
+        for p in points {
             let p1_result = D::eval(&key1, &p).unwrap();
             let p2_result = D::eval(&key2, &p).unwrap();
             if p < x {
@@ -67,6 +94,42 @@ fn test_correctness_helper<D: DIF<F>>() {
     }
 }
 
+fn test_bit_string_helper<D: DIF<F, BitString>>() {
+    let mut rng = test_rng();
+
+    for num_bytes in [1usize, 3, 32] {
+        // Generate a random bit string and field value. The bit strings are whole bytes, so they
+        // compare lexicographically in the same order as their bytes.
+        let log_domain = 8 * num_bytes;
+        let bytes = (0..num_bytes).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let y = F::rand(&mut rng);
+
+        // Create the DIF
+        let func = (log_domain, BitString::from_bytes(bytes.clone()), y);
+        let (key1, key2) = D::gen(&func, &mut rng).unwrap();
+
+        // Evaluate the DIF at the point, at points which differ from it in a single bit, and at
+        // random points
+        let mut points = vec![bytes.clone()];
+        for i in 0..log_domain {
+            let mut flipped = bytes.clone();
+            flipped[i / 8] ^= 0x80 >> (i % 8);
+            points.push(flipped);
+        }
+        points.extend((0..20).map(|_| (0..num_bytes).map(|_| rng.gen()).collect()));
+        for p in points {
+            let point = BitString::from_bytes(p.clone());
+            let p1_result = D::eval(&key1, &point).unwrap();
+            let p2_result = D::eval(&key2, &point).unwrap();
+            if p < bytes {
+                assert!(D::decode((&p1_result, &p2_result)).unwrap() == y)
+            } else {
+                assert!(D::decode((&p1_result, &p2_result)).unwrap() == F::zero())
+            }
+        }
+    }
+}
+
 fn test_bad_inputs_helper<D: DIF<F>>() {
     let mut rng = test_rng();
     let log_domain: usize = 10;
@@ -92,12 +155,450 @@ fn test_bad_inputs_helper<D: DIF<F>>() {
     assert!(result.is_err());
 }
 
+fn test_full_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = IFDescription<F>, Domain = usize>,
+{
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..12 {
+        // Generate a random point in the given domain and field value
+        let x = rng.gen_range(0..(1 << log_domain));
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, x, y);
+        let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        // Expand the full domain and ensure it matches pointwise evaluation
+        let p1_results = TreeScheme::<F, PRG, T>::full_eval(&key1).unwrap();
+        let p2_results = TreeScheme::<F, PRG, T>::full_eval(&key2).unwrap();
+        assert_eq!(p1_results.len(), 1 << log_domain);
+        assert_eq!(p2_results.len(), 1 << log_domain);
+
+        for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+            assert!(*p1_result == TreeScheme::<F, PRG, T>::eval(&key1, &p).unwrap());
+            assert!(*p2_result == TreeScheme::<F, PRG, T>::eval(&key2, &p).unwrap());
+            let result = TreeScheme::<F, PRG, T>::decode((p1_result, p2_result)).unwrap();
+            if p < x {
+                assert!(result == y)
+            } else {
+                assert!(result == F::zero())
+            }
+        }
+    }
+}
+
+fn test_batch_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = IFDescription<F>, Domain = usize>,
+{
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..12 {
+        // Generate a random point in the given domain and field value
+        let max = 1 << log_domain;
+        let x = rng.gen_range(0..max);
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, x, y);
+        let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        // Query a random, unsorted batch of points which includes `x` and some duplicates
+        let mut points: Vec<usize> = (0..max).map(|_| rng.gen_range(0..max)).collect();
+        points.push(x);
+        points.push(points[0]);
+
+        // Ensure that batch evaluation matches pointwise evaluation
+        let p1_results = TreeScheme::<F, PRG, T>::batch_eval(&key1, &points).unwrap();
+        let p2_results = TreeScheme::<F, PRG, T>::batch_eval(&key2, &points).unwrap();
+        assert_eq!(p1_results.len(), points.len());
+        assert_eq!(p2_results.len(), points.len());
+        for (i, p) in points.iter().enumerate() {
+            assert!(p1_results[i] == TreeScheme::<F, PRG, T>::eval(&key1, p).unwrap());
+            assert!(p2_results[i] == TreeScheme::<F, PRG, T>::eval(&key2, p).unwrap());
+        }
+
+        // An empty batch produces no shares, while a batch with a bad point fails
+        assert!(TreeScheme::<F, PRG, T>::batch_eval(&key1, &[])
+            .unwrap()
+            .is_empty());
+        points.push(max);
+        assert!(TreeScheme::<F, PRG, T>::batch_eval(&key1, &points).is_err());
+    }
+}
+
+fn test_eval_range_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = IFDescription<F>, Domain = usize>,
+{
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..12 {
+        // Generate a random point in the given domain and field value
+        let max = 1 << log_domain;
+        let x = rng.gen_range(0..max);
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, x, y);
+        let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        // Evaluate a random range and ensure it matches pointwise evaluation
+        let start = rng.gen_range(0..=max);
+        let end = rng.gen_range(start..=max);
+        let p1_results = TreeScheme::<F, PRG, T>::eval_range(&key1, start..end).unwrap();
+        let p2_results = TreeScheme::<F, PRG, T>::eval_range(&key2, start..end).unwrap();
+        assert_eq!(p1_results.len(), end - start);
+        assert_eq!(p2_results.len(), end - start);
+
+        for (p, (p1_result, p2_result)) in (start..end).zip(p1_results.iter().zip(&p2_results)) {
+            assert!(*p1_result == TreeScheme::<F, PRG, T>::eval(&key1, &p).unwrap());
+            assert!(*p2_result == TreeScheme::<F, PRG, T>::eval(&key2, &p).unwrap());
+            let result = TreeScheme::<F, PRG, T>::decode((p1_result, p2_result)).unwrap();
+            if p < x {
+                assert!(result == y)
+            } else {
+                assert!(result == F::zero())
+            }
+        }
+
+        // Ranges which leave the domain fail
+        assert!(TreeScheme::<F, PRG, T>::eval_range(&key1, start..max + 1).is_err());
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn test_parallel_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = IFDescription<F>, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+{
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..12 {
+        // Generate a random point in the given domain and field value
+        let max = 1 << log_domain;
+        let x = rng.gen_range(0..max);
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, x, y);
+        let (key, _) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        // Ensure that the multi-threaded evaluations match the single-threaded ones, including
+        // when the split depth is out of bounds
+        let full = TreeScheme::<F, PRG, T>::full_eval(&key).unwrap();
+        let start = rng.gen_range(0..=max);
+        let end = rng.gen_range(start..=max);
+        let points: Vec<usize> = (0..max).map(|_| rng.gen_range(0..max)).collect();
+        let batch = TreeScheme::<F, PRG, T>::batch_eval(&key, &points).unwrap();
+
+        for split_depth in [0, 1, log_domain / 2, log_domain, log_domain + 1] {
+            let par_full = TreeScheme::<F, PRG, T>::par_full_eval(&key, split_depth).unwrap();
+            assert!(par_full == full);
+
+            let par_range =
+                TreeScheme::<F, PRG, T>::par_eval_range(&key, start..end, split_depth).unwrap();
+            assert!(par_range == full[start..end]);
+
+            let par_batch =
+                TreeScheme::<F, PRG, T>::par_batch_eval(&key, &points, split_depth).unwrap();
+            assert!(par_batch == batch);
+        }
+    }
+}
+
+fn test_key_ownership_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = IFDescription<F>, Domain = usize> + 'static,
+    T::Root: Send + Sync,
+    T::Codeword: Send + Sync,
+{
+    fn assert_send_sync<K: Send + Sync>(_: &K) {}
+
+    let mut rng = test_rng();
+    let log_domain: usize = 10;
+    let x = rng.gen_range(0..(1 << log_domain));
+    let y = F::rand(&mut rng);
+
+    // Both keys initially share their codewords
+    let func = (log_domain, x, y);
+    let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+    assert!(!key1.is_owned() && !key2.is_owned());
+
+    // A deserialized key owns its codewords and evaluates identically
+    let mut serialized = vec![0; key1.serialized_size()];
+    key1.serialize(&mut serialized[..]).unwrap();
+    let recovered = TreeKey::<F, PRG, T>::deserialize(serialized.as_slice()).unwrap();
+    assert!(recovered.is_owned());
+    assert!(
+        TreeScheme::<F, PRG, T>::full_eval(&recovered).unwrap()
+            == TreeScheme::<F, PRG, T>::full_eval(&key1).unwrap()
+    );
+
+    // Once one key takes ownership, neither key is shared
+    let key1 = key1.into_owned();
+    assert!(key1.is_owned() && key2.is_owned());
+
+    // Evaluate each key on its own thread
+    let [p1_results, p2_results] = [key1, key2].map(|key| {
+        assert_send_sync(&key);
+        std::thread::spawn(move || TreeScheme::<F, PRG, T>::full_eval(&key).unwrap())
+            .join()
+            .unwrap()
+    });
+    for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+        let result = TreeScheme::<F, PRG, T>::decode((p1_result, p2_result)).unwrap();
+        if p < x {
+            assert!(result == y)
+        } else {
+            assert!(result == F::zero())
+        }
+    }
+}
+
+/// Returns whether `x` is in the interval `[a, b]`, which wraps around the domain if `a > b`
+fn in_interval(a: usize, b: usize, x: usize) -> bool {
+    match a <= b {
+        true => a <= x && x <= b,
+        false => x >= a || x <= b,
+    }
+}
+
+fn test_two_sided_correctness_helper<G: AbelianGroup, D: TwoSidedDIF<G>>() {
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..10 {
+        // Generate random endpoints in the given domain, as well as the edge cases where the
+        // interval ends at the last point, contains a single point, wraps around, or covers the
+        // entire domain
+        let max = 1 << log_domain;
+        let a = rng.gen_range(0..max);
+        let b = rng.gen_range(0..max);
+        let y = G::group_sample(&mut rng);
+
+        for (a, b) in [
+            (a, b),
+            (b, a),
+            (a, max - 1),
+            (a, a),
+            (a + 1, a),
+            (0, max - 1),
+        ] {
+            let a = a % max;
+
+            // Create the keys
+            let func = (log_domain, a, b, y);
+            let (key1, key2) = D::gen(&func, &mut rng).unwrap();
+
+            // Evaluate each point of the interval function
+            for p in 0..max {
+                let p1_result = D::eval(&key1, &p).unwrap();
+                let p2_result = D::eval(&key2, &p).unwrap();
+                let result = D::decode((&p1_result, &p2_result)).unwrap();
+                if in_interval(a, b, p) {
+                    assert!(result == y)
+                } else {
+                    assert!(result == G::group_zero())
+                }
+            }
+
+            // Points outside the domain fail
+            assert!(D::eval(&key1, &max).is_err());
+        }
+
+        // Endpoints outside the domain fail
+        assert!(D::gen(&(log_domain, max, b, y), &mut rng).is_err());
+        assert!(D::gen(&(log_domain, a, max, y), &mut rng).is_err());
+    }
+
+    // In a domain of `2^64` points, intervals ending at the last point wrap the upper endpoint
+    // around to 0
+    let last = usize::MAX;
+    let a = rng.gen::<usize>();
+    let y = G::group_sample(&mut rng);
+    for (a, b) in [(a, last), (last, a), (0, last), (last, last)] {
+        let (key1, key2) = D::gen(&(64, a, b, y), &mut rng).unwrap();
+        for p in [
+            0,
+            1,
+            a.wrapping_sub(1),
+            a,
+            a.wrapping_add(1),
+            last - 1,
+            last,
+        ] {
+            let p1_result = D::eval(&key1, &p).unwrap();
+            let p2_result = D::eval(&key2, &p).unwrap();
+            let result = D::decode((&p1_result, &p2_result)).unwrap();
+            if in_interval(a, b, p) {
+                assert!(result == y)
+            } else {
+                assert!(result == G::group_zero())
+            }
+        }
+    }
+}
+
+fn test_two_sided_tree_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = IFDescription<F>, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+    TreeScheme<F, PRG, T>: DIF<F, Key = TreeKey<F, PRG, T>, Share = F>,
+{
+    type D<T> = two_sided::TwoSided<F, TreeScheme<F, PRG, T>>;
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..10 {
+        // Generate a random, possibly wrapping, interval in the given domain
+        let max = 1 << log_domain;
+        let a = rng.gen_range(0..max);
+        let b = rng.gen_range(0..max);
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, a, b, y);
+        let (key1, key2) = D::<T>::gen(&func, &mut rng).unwrap();
+
+        // Ensure that full-domain, range, and batch evaluation match pointwise evaluation
+        let p1_results = D::<T>::full_eval(&key1).unwrap();
+        let p2_results = D::<T>::full_eval(&key2).unwrap();
+        for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+            assert!(*p1_result == D::<T>::eval(&key1, &p).unwrap());
+            let result = D::<T>::decode((p1_result, p2_result)).unwrap();
+            if in_interval(a, b, p) {
+                assert!(result == y)
+            } else {
+                assert!(result == F::zero())
+            }
+        }
+
+        let start = rng.gen_range(0..=max);
+        let end = rng.gen_range(start..=max);
+        let range = D::<T>::eval_range(&key1, start..end).unwrap();
+        assert!(range == p1_results[start..end]);
+
+        let points: Vec<usize> = (0..max).map(|_| rng.gen_range(0..max)).collect();
+        let batch = D::<T>::batch_eval(&key1, &points).unwrap();
+        for (i, p) in points.iter().enumerate() {
+            assert!(batch[i] == p1_results[*p]);
+        }
+
+        #[cfg(feature = "parallel")]
+        for split_depth in [0, log_domain / 2, log_domain + 1] {
+            assert!(D::<T>::par_full_eval(&key1, split_depth).unwrap() == p1_results);
+            assert!(D::<T>::par_eval_range(&key1, start..end, split_depth).unwrap() == range);
+            assert!(D::<T>::par_batch_eval(&key1, &points, split_depth).unwrap() == batch);
+        }
+    }
+}
+
 #[test]
 fn test_correctness() {
-    super::tests::test_correctness_helper::<BGI15>();
+    super::tests::test_correctness_helper::<F, BGI15>();
+    super::tests::test_correctness_helper::<F, AesBGI15>();
+    super::tests::test_correctness_helper::<F, BCG21>();
+    super::tests::test_correctness_helper::<F, AesBCG21>();
+}
+
+#[test]
+fn test_ring_correctness() {
+    super::tests::test_correctness_helper::<R, RingBGI15>();
+    super::tests::test_correctness_helper::<R, RingBCG21>();
+    super::tests::test_correctness_helper::<Z2k<u8>, bcg21::Bcg21DIF<Z2k<u8>, PRG>>();
+    super::tests::test_two_sided_correctness_helper::<R, RingTwoSidedBCG21>();
+}
+
+#[test]
+fn test_group_correctness() {
+    super::tests::test_correctness_helper::<G, GroupBGI15>();
+    super::tests::test_correctness_helper::<[F; 4], ArrayBGI15>();
+    super::tests::test_two_sided_correctness_helper::<[F; 4], ArrayTwoSidedBGI15>();
+}
+
+#[test]
+fn test_large_domain_correctness() {
+    super::tests::test_large_domain_helper::<LargeBGI15>();
+    super::tests::test_large_domain_helper::<LargeBCG21>();
+}
+
+#[test]
+fn test_bit_string_correctness() {
+    super::tests::test_bit_string_helper::<BitStringBGI15>();
+    super::tests::test_bit_string_helper::<BitStringBCG21>();
 }
 
 #[test]
 fn test_bad_inputs() {
     super::tests::test_bad_inputs_helper::<BGI15>();
+    super::tests::test_bad_inputs_helper::<BCG21>();
+}
+
+#[test]
+fn test_full_eval() {
+    super::tests::test_full_eval_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_full_eval_helper::<bcg21::Bcg21<F, PRG>>();
+}
+
+#[test]
+fn test_batch_eval() {
+    super::tests::test_batch_eval_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_batch_eval_helper::<bcg21::Bcg21<F, PRG>>();
+}
+
+#[test]
+fn test_eval_range() {
+    super::tests::test_eval_range_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_eval_range_helper::<bcg21::Bcg21<F, PRG>>();
+}
+
+#[test]
+fn test_key_ownership() {
+    super::tests::test_key_ownership_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_key_ownership_helper::<bcg21::Bcg21<F, PRG>>();
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_eval() {
+    super::tests::test_parallel_eval_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_parallel_eval_helper::<bcg21::Bcg21<F, PRG>>();
+}
+
+#[test]
+fn test_key_size() {
+    let mut rng = test_rng();
+    let log_domain: usize = 20;
+    let func = (
+        log_domain,
+        rng.gen_range(0..(1 << log_domain)),
+        F::rand(&mut rng),
+    );
+
+    // [BCG+21] has a quarter of the codeword seeds and field elements of [BGI15]
+    let (bgi15_key, _) = BGI15::gen(&func, &mut rng).unwrap();
+    let (bcg21_key, _) = BCG21::gen(&func, &mut rng).unwrap();
+    let seed_len = <PRG as TreePrg>::Seed::default().len();
+    let elem_len = F::zero().serialized_size();
+    let level_len = seed_len + elem_len;
+    assert!(bgi15_key.codewords.as_ref().serialized_size() >= 4 * level_len * (log_domain - 1));
+    assert!(bcg21_key.codewords.as_ref().serialized_size() <= 2 * level_len * (log_domain - 1));
+}
+
+#[test]
+fn test_two_sided_correctness() {
+    super::tests::test_two_sided_correctness_helper::<F, TwoSidedBGI15>();
+    super::tests::test_two_sided_correctness_helper::<F, TwoSidedBCG21>();
+}
+
+#[test]
+fn test_two_sided_tree_eval() {
+    super::tests::test_two_sided_tree_eval_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_two_sided_tree_eval_helper::<bcg21::Bcg21<F, PRG>>();
 }
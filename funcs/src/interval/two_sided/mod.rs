@@ -0,0 +1,198 @@
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, ops::Range, vec::Vec};
+
+use crate::{
+    domain::{add_mod, check_point},
+    interval::{bcg21, bgi15, TwoSidedDIF, DIF},
+    tree::{TreeFSS, TreeKey, TreeScheme},
+    AbelianGroup, TreePrg, FSS,
+};
+
+/// Two-sided DIF built from the DIF of [[BGI15]].
+///
+/// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
+pub type Bgi15TwoSidedDIF<F, P> = TwoSided<F, bgi15::Bgi15DIF<F, P>>;
+
+/// Two-sided DIF built from the DIF of [[BCG+21]].
+///
+/// [BCG+21]: https://eprint.iacr.org/2020/1392.pdf
+pub type Bcg21TwoSidedDIF<F, P> = TwoSided<F, bcg21::Bcg21DIF<F, P>>;
+
+/// A two-sided interval function built from two keys of the one-sided `DIF` `D`, using
+/// `[a <= x <= b] = [x < b + 1] - [x < a]`.
+///
+/// When `b` is the last point in the domain, or `a > b` so the interval wraps around the end of
+/// the domain, the parties also hold shares of a constant `y` which is corrected by the
+/// one-sided keys. The constant is always secret-shared so keys don't reveal which case they
+/// represent.
+pub struct TwoSided<F, D>
+where
+    F: AbelianGroup,
+    D: DIF<F, Share = F>,
+{
+    _field: PhantomData<F>,
+    _dif: PhantomData<D>,
+}
+
+impl<F, D> TwoSidedDIF<F> for TwoSided<F, D>
+where
+    F: AbelianGroup,
+    D: DIF<F, Share = F>,
+{
+}
+
+/// A key for a two-sided interval function. A party's share is `constant + upper - lower`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TwoSidedKey<F: AbelianGroup, K: Serialize + Deserialize> {
+    pub lower: K,
+    pub upper: K,
+    pub constant: F,
+}
+
+impl<F, D> FSS for TwoSided<F, D>
+where
+    F: AbelianGroup,
+    D: DIF<F, Share = F>,
+{
+    type Key = TwoSidedKey<F, D::Key>;
+    type Description = super::TwoSidedIFDescription<F>;
+    type Domain = usize;
+    type Range = F;
+    type Share = F;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, a, b, val) = *f;
+        check_point(log_domain, b)?;
+
+        // The upper key is at `b + 1`, which wraps around to 0 (i.e. an all-zero function) when
+        // `b` is the last point in the domain. The constant corrects for the points which aren't
+        // less than the upper point, which is exactly when the upper point is at most `a`.
+        let upper = add_mod(log_domain, b, 1);
+        let (p1_lower, p2_lower) = D::gen(&(log_domain, a, val), rng)?;
+        let (p1_upper, p2_upper) = D::gen(&(log_domain, upper, val), rng)?;
+
+        let p2_constant = F::group_sample(rng);
+        let p1_constant = match upper <= a {
+            true => p2_constant.group_add(&val),
+            false => p2_constant,
+        };
+
+        Ok((
+            TwoSidedKey {
+                lower: p1_lower,
+                upper: p1_upper,
+                constant: p1_constant,
+            },
+            TwoSidedKey {
+                lower: p2_lower,
+                upper: p2_upper,
+                constant: p2_constant,
+            },
+        ))
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        let lower = D::eval(&key.lower, point)?;
+        let upper = D::eval(&key.upper, point)?;
+        Ok(key.constant.group_add(&upper).group_sub(&lower))
+    }
+
+    fn decode(shares: (&F, &F)) -> Result<F, Box<dyn Error>> {
+        Ok(shares.0.group_sub(shares.1))
+    }
+}
+
+impl<F, P, T> TwoSided<F, TreeScheme<F, P, T>>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P, Description = super::IFDescription<F>, Domain = usize>,
+    TreeScheme<F, P, T>: DIF<F, Key = TreeKey<F, P, T>, Share = F>,
+{
+    /// Takes a `Key` as input, and outputs a secret share of the underlying function at every
+    /// point in the domain, ordered by point.
+    pub fn full_eval(key: &TwoSidedKey<F, TreeKey<F, P, T>>) -> Result<Vec<F>, Box<dyn Error>> {
+        let lower = TreeScheme::<F, P, T>::full_eval(&key.lower)?;
+        let upper = TreeScheme::<F, P, T>::full_eval(&key.upper)?;
+        Ok(Self::combine(key, lower, upper))
+    }
+
+    /// Takes a `Key` and a range of points as input, and outputs a secret share of the underlying
+    /// function at each point in the range, ordered by point.
+    pub fn eval_range(
+        key: &TwoSidedKey<F, TreeKey<F, P, T>>,
+        range: Range<usize>,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        let lower = TreeScheme::<F, P, T>::eval_range(&key.lower, range.clone())?;
+        let upper = TreeScheme::<F, P, T>::eval_range(&key.upper, range)?;
+        Ok(Self::combine(key, lower, upper))
+    }
+
+    /// Takes a `Key` and a batch of points as input, and outputs a secret share of the underlying
+    /// function at each point, in the same order as `points`.
+    pub fn batch_eval(
+        key: &TwoSidedKey<F, TreeKey<F, P, T>>,
+        points: &[usize],
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        let lower = TreeScheme::<F, P, T>::batch_eval(&key.lower, points)?;
+        let upper = TreeScheme::<F, P, T>::batch_eval(&key.upper, points)?;
+        Ok(Self::combine(key, lower, upper))
+    }
+
+    /// Combines the shares output by the one-sided keys into shares of the two-sided function
+    fn combine(key: &TwoSidedKey<F, TreeKey<F, P, T>>, lower: Vec<F>, mut upper: Vec<F>) -> Vec<F> {
+        upper
+            .iter_mut()
+            .zip(lower)
+            .for_each(|(u, l)| *u = key.constant.group_add(u).group_sub(&l));
+        upper
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<F, P, T> TwoSided<F, TreeScheme<F, P, T>>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P, Description = super::IFDescription<F>, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+    TreeScheme<F, P, T>: DIF<F, Key = TreeKey<F, P, T>, Share = F>,
+{
+    /// A multi-threaded version of `full_eval`. See `TreeScheme::par_full_eval`.
+    pub fn par_full_eval(
+        key: &TwoSidedKey<F, TreeKey<F, P, T>>,
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        let lower = TreeScheme::<F, P, T>::par_full_eval(&key.lower, split_depth)?;
+        let upper = TreeScheme::<F, P, T>::par_full_eval(&key.upper, split_depth)?;
+        Ok(Self::combine(key, lower, upper))
+    }
+
+    /// A multi-threaded version of `eval_range`. See `TreeScheme::par_eval_range`.
+    pub fn par_eval_range(
+        key: &TwoSidedKey<F, TreeKey<F, P, T>>,
+        range: Range<usize>,
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        let lower = TreeScheme::<F, P, T>::par_eval_range(&key.lower, range.clone(), split_depth)?;
+        let upper = TreeScheme::<F, P, T>::par_eval_range(&key.upper, range, split_depth)?;
+        Ok(Self::combine(key, lower, upper))
+    }
+
+    /// A multi-threaded version of `batch_eval`. See `TreeScheme::par_batch_eval`.
+    pub fn par_batch_eval(
+        key: &TwoSidedKey<F, TreeKey<F, P, T>>,
+        points: &[usize],
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        let lower = TreeScheme::<F, P, T>::par_batch_eval(&key.lower, points, split_depth)?;
+        let upper = TreeScheme::<F, P, T>::par_batch_eval(&key.upper, points, split_depth)?;
+        Ok(Self::combine(key, lower, upper))
+    }
+}
@@ -3,15 +3,35 @@ use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as S
 use rand::{CryptoRng, RngCore};
 use std::error::Error;
 
+pub mod gates;
 pub mod interval;
+pub mod multi_point;
 pub mod point;
 
+pub mod domain;
+pub use domain::*;
+
+pub mod group;
+pub use group::*;
+
+pub mod ring;
+pub use ring::*;
+
+pub mod prg;
+pub use prg::*;
+
 pub mod tree;
 pub use tree::*;
 
+pub mod sum;
+pub use sum::*;
+
 pub mod data_structures;
 pub use data_structures::*;
 
+#[cfg(test)]
+pub(crate) mod test_field;
+
 /// Describes the interface for a function secret sharing scheme. Such a scheme
 /// allows a sender to generate keys which provide succinct representations of functions
 /// which output secret shares of the underlying function.
@@ -51,23 +71,5 @@ pub trait FSS {
 /// Helper function to convert a `usize` to a vector of bools in big endian format
 #[inline]
 fn usize_to_bits(log_domain: usize, val: usize) -> Result<Vec<bool>, Box<dyn Error>> {
-    // Ensure that the point is valid in the given domain
-    if val >= (1 << log_domain) {
-        return Err("Input point is not contained in provided domain")?;
-    }
-
-    let mut bits = Vec::new();
-    bits.reserve(log_domain - 1);
-
-    // Compute the little-endian bit-decomposition
-    let bytes = val.to_le_bytes();
-    for i in 0..log_domain {
-        let mask = 1 << (i % 8);
-        let bit = mask & bytes[(i / 8) as usize];
-        bits.push(bit != 0);
-    }
-
-    // Convert to big-endian and return
-    bits.reverse();
-    Ok(bits)
+    domain::le_bytes_to_bits(log_domain, &val.to_le_bytes())
 }
@@ -0,0 +1,379 @@
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, Rng, RngCore};
+use std::{collections::VecDeque, error::Error, marker::PhantomData, vec::Vec};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    domain::{domain_size, last_point},
+    multi_point::MPFSS,
+    point::{bgi16::Bgi16DPF, HidesZero},
+    tree::{TreeFSS, TreeKey, TreeScheme},
+    AbelianGroup, TreePrg, FSS,
+};
+
+/// MPFSS scheme using a DPF based on [[BGI16]] for each bucket.
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub type Bgi16CuckooMPFSS<G, P> = Cuckoo<G, Bgi16DPF<G, P>>;
+
+/// The number of hash functions used to place each point in buckets
+const NUM_HASHES: usize = 3;
+
+/// The minimum number of buckets for each hash function, which bounds the probability that the
+/// points can't be cuckoo hashed for small `t`
+const MIN_BUCKETS_PER_HASH: usize = 256;
+
+/// The number of rounds of the Feistel network used as each hash function
+const FEISTEL_ROUNDS: usize = 6;
+
+/// An MPFSS scheme built from the DPF `S`, following [[SGRR19]].
+///
+/// Each of 3 public hash functions is a pseudorandom permutation of the domain, which splits the
+/// domain into `b` buckets of `2^n / b` consecutive permuted points, so every point is in exactly
+/// one bucket of each hash function, at a position given by its permuted value. The `t` non-zero
+/// points are cuckoo hashed into these `3b` buckets so that each bucket contains at most one of
+/// them. Each bucket is secret-shared by a DPF over the positions in that bucket, which is
+/// non-zero at the position of the bucket's point, if it has one. A point's share is the sum of
+/// its shares from each of its buckets.
+///
+/// `b` is the smallest power of two which is at least `max(t / 2, 256)`, up to `2^(n - 1)`. With
+/// at most 2 points for every 3 buckets, and at least 256 buckets for each hash function, a union
+/// bound over the sets of 4 points which are hashed into the same 3 buckets shows that the
+/// points fail to be cuckoo hashed with probability at most about `2^-40`, in which case `gen`
+/// fails. The hash functions are sampled independently of the points, and never resampled.
+///
+/// The buckets contain `3 * 2^n` points in total, so full-domain evaluation expands `3 * 2^n`
+/// leaves regardless of `t`, rather than `t * 2^n` for a DPF key per point, and `eval` evaluates
+/// a DPF in each of the point's 3 buckets.
+///
+/// A key always holds at least 768 DPF keys, even for a single point, and full-domain evaluation
+/// also hashes every point of the domain. So for small `t`, a `SumFSS` of `t` DPF keys is the
+/// better choice: for domains of `2^16` to `2^40` points, its keys are smaller for `t` up to
+/// about 400 to 600, its `eval` is faster for `t` below about 3, and its `full_eval` is faster
+/// for `t` below about 8.
+///
+/// Keys of empty buckets share the zero function, so `S` must hide whether the value of a point
+/// function is zero, which rules out e.g. the DPF of [[BGI15]].
+///
+/// [SGRR19]: https://eprint.iacr.org/2019/1084.pdf
+/// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
+pub struct Cuckoo<G, S>
+where
+    G: AbelianGroup,
+    S: HidesZero<G, Share = G>,
+{
+    _group: PhantomData<G>,
+    _dpf: PhantomData<S>,
+}
+
+impl<G, S> MPFSS<G> for Cuckoo<G, S>
+where
+    G: AbelianGroup,
+    S: HidesZero<G, Share = G>,
+{
+}
+
+/// A key for a multi-point function. The hash functions, and so the number of buckets, are
+/// public and identical for both parties. `keys` holds the `b` buckets of each hash function in
+/// turn.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CuckooKey<K: Serialize + Deserialize> {
+    pub log_domain: usize,
+    pub hash_keys: Vec<u64>,
+    pub keys: Vec<K>,
+}
+
+impl<G, S> FSS for Cuckoo<G, S>
+where
+    G: AbelianGroup,
+    S: HidesZero<G, Share = G>,
+{
+    type Key = CuckooKey<S::Key>;
+    type Description = super::MPFDescription<G>;
+    type Domain = usize;
+    type Range = G;
+    type Share = G;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, points) = f;
+        let log_domain = *log_domain;
+        if log_domain == 0 {
+            return Err("Cuckoo(): Domain must contain at least two points".into());
+        }
+        if points.is_empty() {
+            return Err("Cuckoo(): The function must have at least one point".into());
+        }
+        let last = last_point(log_domain)?;
+        if points.iter().any(|(x, _)| *x > last) {
+            return Err("Input point is not contained in provided domain".into());
+        }
+        let mut xs = points.iter().map(|(x, _)| *x).collect::<Vec<_>>();
+        xs.sort_unstable();
+        xs.dedup();
+        if xs.len() != points.len() {
+            return Err("Cuckoo(): The points must be distinct".into());
+        }
+
+        // Sample the hash functions once, and place each point in one of its buckets
+        let log_buckets = points
+            .len()
+            .div_ceil(2)
+            .max(MIN_BUCKETS_PER_HASH)
+            .next_power_of_two()
+            .trailing_zeros() as usize;
+        let log_buckets = log_buckets.min(log_domain - 1);
+        let hash_keys = (0..NUM_HASHES).map(|_| rng.gen()).collect::<Vec<u64>>();
+        let locations = points
+            .iter()
+            .map(|(x, _)| locations(&hash_keys, log_domain, log_buckets, *x))
+            .collect::<Vec<_>>();
+        let table = cuckoo_hash(&locations, NUM_HASHES << log_buckets)
+            .ok_or("Cuckoo(): Failed to hash the points into buckets")?;
+
+        // Buckets without a point share the zero function
+        let log_bucket_size = log_domain - log_buckets;
+        let mut p1_keys = Vec::with_capacity(table.len());
+        let mut p2_keys = Vec::with_capacity(table.len());
+        for (j, entry) in table.iter().enumerate() {
+            let (position, val) = match entry {
+                Some(i) => (position_in(&locations[*i], j), points[*i].1),
+                None => (0, G::group_zero()),
+            };
+            let (p1_key, p2_key) = S::gen(&(log_bucket_size, position, val), rng)?;
+            p1_keys.push(p1_key);
+            p2_keys.push(p2_key);
+        }
+
+        Ok((
+            CuckooKey {
+                log_domain,
+                hash_keys: hash_keys.clone(),
+                keys: p1_keys,
+            },
+            CuckooKey {
+                log_domain,
+                hash_keys,
+                keys: p2_keys,
+            },
+        ))
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<G, Box<dyn Error>> {
+        let log_buckets = check_key(key)?;
+        if *point > last_point(key.log_domain)? {
+            return Err("Input point is not contained in provided domain".into());
+        }
+
+        let mut share = G::group_zero();
+        for (j, position) in locations(&key.hash_keys, key.log_domain, log_buckets, *point) {
+            share = share.group_add(&S::eval(&key.keys[j], &position)?);
+        }
+        Ok(share)
+    }
+
+    fn decode(shares: (&G, &G)) -> Result<G, Box<dyn Error>> {
+        Ok(shares.0.group_sub(shares.1))
+    }
+}
+
+impl<G, P, T> Cuckoo<G, TreeScheme<G, P, T>>
+where
+    G: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<G, P, Description = (usize, usize, G), Domain = usize>,
+    TreeScheme<G, P, T>: HidesZero<G, Key = TreeKey<G, P, T>, Share = G>,
+{
+    /// Takes a `Key` as input, and outputs a secret share of the underlying function at every
+    /// point in the domain, ordered by point.
+    ///
+    /// Each bucket's DPF is expanded over the positions in the bucket, and the shares of each
+    /// point in its buckets are summed.
+    pub fn full_eval(key: &CuckooKey<TreeKey<G, P, T>>) -> Result<Vec<G>, Box<dyn Error>> {
+        check_key(key)?;
+        let bucket_shares = key
+            .keys
+            .iter()
+            .map(TreeScheme::<G, P, T>::full_eval)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::reassemble(key, bucket_shares)
+    }
+
+    /// Sums the shares of each point in the buckets it is hashed to
+    fn reassemble(
+        key: &CuckooKey<TreeKey<G, P, T>>,
+        bucket_shares: Vec<Vec<G>>,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        let log_buckets = check_key(key)?;
+        let mut shares = vec![G::group_zero(); domain_size(key.log_domain)?];
+        for (x, share) in shares.iter_mut().enumerate() {
+            for (j, position) in locations(&key.hash_keys, key.log_domain, log_buckets, x) {
+                let bucket_share = bucket_shares[j]
+                    .get(position)
+                    .ok_or("Eval(): Bucket is smaller than the points hashed to it")?;
+                *share = share.group_add(bucket_share);
+            }
+        }
+        Ok(shares)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<G, P, T> Cuckoo<G, TreeScheme<G, P, T>>
+where
+    G: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<G, P, Description = (usize, usize, G), Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+    TreeScheme<G, P, T>: HidesZero<G, Key = TreeKey<G, P, T>, Share = G>,
+{
+    /// A multi-threaded version of `full_eval`, which expands the buckets on the rayon thread
+    /// pool.
+    pub fn par_full_eval(key: &CuckooKey<TreeKey<G, P, T>>) -> Result<Vec<G>, Box<dyn Error>> {
+        check_key(key)?;
+        let bucket_shares = key
+            .keys
+            .par_iter()
+            .map(|k| TreeScheme::<G, P, T>::full_eval(k).map_err(|e| e.to_string()))
+            .collect::<Result<Vec<_>, String>>()?;
+        Self::reassemble(key, bucket_shares)
+    }
+}
+
+/// Ensures that `key` has a hash key for each hash function, and the same power of two number
+/// of buckets for each of them, and outputs the logarithm of that number
+#[inline]
+fn check_key<K: Serialize + Deserialize>(key: &CuckooKey<K>) -> Result<usize, Box<dyn Error>> {
+    let buckets = key.keys.len() / NUM_HASHES;
+    let log_buckets = buckets.trailing_zeros() as usize;
+    if key.hash_keys.len() != NUM_HASHES
+        || key.keys.len() != NUM_HASHES * buckets
+        || !buckets.is_power_of_two()
+        || log_buckets >= key.log_domain
+    {
+        return Err("Eval(): Key has the wrong number of hash functions or buckets".into());
+    }
+    Ok(log_buckets)
+}
+
+/// The finalizer of MurmurHash3, which mixes each bit of the input into every bit of the output
+#[inline]
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 33)).wrapping_mul(0xff51afd7ed558ccd);
+    z = (z ^ (z >> 33)).wrapping_mul(0xc4ceb9fe1a85ec53);
+    z ^ (z >> 33)
+}
+
+/// Permutes the domain of `2^log_domain` points with a Feistel network keyed by `hash_key`.
+///
+/// The point is split into a left and a right half, and each round maps `(L, R)` to
+/// `(R, L ^ F(R))`, which swaps the widths of the halves when `log_domain` is odd. Every round is
+/// a permutation, and there are an even number of rounds, so the halves end at their original
+/// widths.
+#[inline]
+fn permute(hash_key: u64, log_domain: usize, x: usize) -> usize {
+    let (mut left_bits, mut right_bits) = (log_domain / 2, log_domain - log_domain / 2);
+    let (mut left, mut right) = (x as u64 >> right_bits, x as u64 & low_bits(right_bits));
+    for round in 0..FEISTEL_ROUNDS {
+        let round_key = mix(hash_key.wrapping_add(round as u64 + 1));
+        let f = mix(right ^ round_key) & low_bits(left_bits);
+        (left, right) = (right, left ^ f);
+        (left_bits, right_bits) = (right_bits, left_bits);
+    }
+    ((left << right_bits) | right) as usize
+}
+
+/// Outputs the mask of the low `bits` bits of a `u64`, for `bits` less than 64
+#[inline]
+fn low_bits(bits: usize) -> u64 {
+    (1 << bits) - 1
+}
+
+/// Outputs the bucket which `x` is hashed to by each hash function, along with the position of
+/// `x` in that bucket
+#[inline]
+fn locations(
+    hash_keys: &[u64],
+    log_domain: usize,
+    log_buckets: usize,
+    x: usize,
+) -> [(usize, usize); NUM_HASHES] {
+    let log_bucket_size = log_domain - log_buckets;
+    std::array::from_fn(|k| {
+        let y = permute(hash_keys[k], log_domain, x);
+        let bucket = (k << log_buckets) + (y >> log_bucket_size);
+        (bucket, (y as u64 & low_bits(log_bucket_size)) as usize)
+    })
+}
+
+/// Outputs the position of a point in the bucket `j`, which is one of its `locations`
+#[inline]
+fn position_in(locations: &[(usize, usize); NUM_HASHES], j: usize) -> usize {
+    locations
+        .iter()
+        .find(|(bucket, _)| *bucket == j)
+        .map_or(0, |(_, position)| *position)
+}
+
+/// Assigns each point to one of the buckets in its `locations` so that each bucket contains at
+/// most one point. Each point is inserted by a breadth-first search for the shortest sequence of
+/// evictions which ends at an empty bucket, so this only fails if no assignment exists. Outputs
+/// the index of the point in each bucket, or `None` on failure.
+fn cuckoo_hash(
+    locations: &[[(usize, usize); NUM_HASHES]],
+    num_buckets: usize,
+) -> Option<Vec<Option<usize>>> {
+    let mut table: Vec<Option<usize>> = vec![None; num_buckets];
+
+    // `parent[j]` is the bucket whose point would be evicted into bucket `j` in the current
+    // search. `visited` lists the buckets to reset after each search.
+    let mut parent: Vec<Option<Option<usize>>> = vec![None; num_buckets];
+    let mut visited = Vec::new();
+    let mut queue = VecDeque::new();
+
+    for (i, point_locations) in locations.iter().enumerate() {
+        for (j, _) in point_locations {
+            if parent[*j].is_none() {
+                parent[*j] = Some(None);
+                visited.push(*j);
+                queue.push_back(*j);
+            }
+        }
+
+        let mut empty = None;
+        while let Some(j) = queue.pop_front() {
+            let occupant = match table[j] {
+                Some(occupant) => occupant,
+                None => {
+                    empty = Some(j);
+                    break;
+                }
+            };
+            for (next, _) in &locations[occupant] {
+                if parent[*next].is_none() {
+                    parent[*next] = Some(Some(j));
+                    visited.push(*next);
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        // Move each point along the path of evictions, and place the new point at its start
+        let mut j = empty?;
+        while let Some(Some(previous)) = parent[j] {
+            table[j] = table[previous];
+            j = previous;
+        }
+        table[j] = Some(i);
+
+        visited.drain(..).for_each(|j| parent[j] = None);
+        queue.clear();
+    }
+    Some(table)
+}
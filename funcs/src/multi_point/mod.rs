@@ -0,0 +1,27 @@
+//! A module implementing multi-point function secret sharing schemes
+
+use crate::{AbelianGroup, FSS};
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+/// MPFSS scheme which distributes the points into buckets using cuckoo hashing, and
+/// secret-shares each bucket with a small DPF.
+pub mod cuckoo;
+
+/// The domain of a multi-point function
+type MPFDomain = usize;
+
+/// The range of a multi-point function
+type MPFRange<G> = G;
+
+/// The description of a multi-point function: the logarithm of the domain size, and the distinct
+/// points in that domain at which the function is non-zero, each with its value.
+pub(crate) type MPFDescription<G> = (usize, Vec<(usize, G)>);
+
+/// A multi-point function secret sharing (MPFSS) scheme is a type of FSS scheme for functions
+/// which are non-zero on `t` points, e.g. sparse vectors.
+pub trait MPFSS<G: AbelianGroup>:
+    FSS<Domain = MPFDomain, Range = MPFRange<G>, Description = MPFDescription<G>>
+{
+}
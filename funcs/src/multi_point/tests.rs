@@ -0,0 +1,219 @@
+use ark_ff::{One, Zero};
+use ark_std::test_rng;
+use rand::{seq::index::sample, Rng};
+use rand_chacha::ChaChaRng;
+
+use crate::{
+    multi_point::{cuckoo, MPFSS},
+    point::{bgi16, HidesZero, PFDescription},
+    test_field::F,
+    tree::{TreeFSS, TreeKey, TreeScheme},
+    AbelianGroup, FixedKeyAes, Z2k, FSS,
+};
+
+// Set PRG type
+type PRG = ChaChaRng;
+
+// Aliases for various MPFSS types
+type BGI16 = cuckoo::Bgi16CuckooMPFSS<F, PRG>;
+type AesBGI16 = cuckoo::Bgi16CuckooMPFSS<F, FixedKeyAes>;
+
+// Alias for an MPFSS type over the ring `Z_{2^64}`
+type R = Z2k<u64>;
+type RingBGI16 = cuckoo::Bgi16CuckooMPFSS<R, PRG>;
+
+/// Samples `t` distinct random points in the given domain, each with a random group value
+fn sample_points<G: AbelianGroup>(log_domain: usize, t: usize) -> Vec<(usize, G)> {
+    let mut rng = test_rng();
+    sample(&mut rng, 1 << log_domain, t)
+        .into_iter()
+        .map(|x| (x, G::group_sample(&mut rng)))
+        .collect()
+}
+
+/// Outputs the value of the multi-point function with the given points at `p`
+fn expected<G: AbelianGroup>(points: &[(usize, G)], p: usize) -> G {
+    points
+        .iter()
+        .find(|(x, _)| *x == p)
+        .map_or(G::group_zero(), |(_, y)| *y)
+}
+
+fn test_correctness_helper<G: AbelianGroup, M: MPFSS<G>>() {
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..9 {
+        for t in [1, 3, 1 << (log_domain - 1), 1 << log_domain] {
+            // Generate `t` random points in the given domain and group values
+            let points = sample_points::<G>(log_domain, t);
+
+            // Create the MPFSS
+            let func = (log_domain, points.clone());
+            let (key1, key2) = M::gen(&func, &mut rng).unwrap();
+
+            // Evaluate each point of the MPFSS
+            for p in 0..(1 << log_domain) {
+                let p1_result = M::eval(&key1, &p).unwrap();
+                let p2_result = M::eval(&key2, &p).unwrap();
+                let result = M::decode((&p1_result, &p2_result)).unwrap();
+                assert!(result == expected(&points, p));
+            }
+        }
+    }
+}
+
+fn test_bad_inputs_helper<M: MPFSS<F>>() {
+    let mut rng = test_rng();
+    let log_domain: usize = 10;
+    let max = 1 << log_domain;
+
+    // Test Gen fail with no points, duplicate points, or points outside the domain
+    assert!(M::gen(&(log_domain, vec![]), &mut rng).is_err());
+    let func = (
+        log_domain,
+        vec![(3, F::one()), (5, F::one()), (3, F::one())],
+    );
+    assert!(M::gen(&func, &mut rng).is_err());
+    let func = (log_domain, vec![(3, F::one()), (max, F::one())]);
+    assert!(M::gen(&func, &mut rng).is_err());
+
+    // Test Eval fail
+    let func = (log_domain, sample_points(log_domain, 10));
+    let (k1, k2) = M::gen(&func, &mut rng).unwrap();
+    let p = rng.gen_range(max..2 * max);
+    assert!(M::eval(&k1, &p).is_err());
+    assert!(M::eval(&k2, &p).is_err());
+}
+
+fn test_full_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize>,
+    TreeScheme<F, PRG, T>: HidesZero<F, Key = TreeKey<F, PRG, T>, Share = F>,
+{
+    type Cuckoo<T> = cuckoo::Cuckoo<F, TreeScheme<F, PRG, T>>;
+    let mut rng = test_rng();
+
+    for log_domain in 3usize..12 {
+        for t in [1, 5, 1 << (log_domain - 1)] {
+            // Generate `t` random points in the given domain and field values
+            let points = sample_points::<F>(log_domain, t);
+
+            // Create the keys. Each hash function has a power of two number of buckets, which is
+            // at least `t / 2` and 256 unless the domain is small, and each point of the domain is
+            // in exactly 3 buckets.
+            let func = (log_domain, points.clone());
+            let (key1, key2) = Cuckoo::<T>::gen(&func, &mut rng).unwrap();
+            let buckets = t
+                .div_ceil(2)
+                .max(256)
+                .next_power_of_two()
+                .min(1 << (log_domain - 1));
+            assert_eq!(key1.keys.len(), 3 * buckets);
+            let bucket_sizes = key1.keys.iter().map(|k| 1 << k.log_domain).sum::<usize>();
+            assert_eq!(bucket_sizes, 3 * (1 << log_domain));
+
+            // Expand the full domain and ensure it matches pointwise evaluation
+            let p1_results = Cuckoo::<T>::full_eval(&key1).unwrap();
+            let p2_results = Cuckoo::<T>::full_eval(&key2).unwrap();
+            assert_eq!(p1_results.len(), 1 << log_domain);
+            assert_eq!(p2_results.len(), 1 << log_domain);
+
+            for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+                assert!(*p1_result == Cuckoo::<T>::eval(&key1, &p).unwrap());
+                assert!(*p2_result == Cuckoo::<T>::eval(&key2, &p).unwrap());
+                let result = Cuckoo::<T>::decode((p1_result, p2_result)).unwrap();
+                assert!(result == expected(&points, p));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn test_parallel_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+    TreeScheme<F, PRG, T>: HidesZero<F, Key = TreeKey<F, PRG, T>, Share = F>,
+{
+    type Cuckoo<T> = cuckoo::Cuckoo<F, TreeScheme<F, PRG, T>>;
+    let mut rng = test_rng();
+
+    for log_domain in 3usize..12 {
+        // Generate random points in the given domain and field values
+        let points = sample_points::<F>(log_domain, 1 << (log_domain - 2));
+
+        // Expanding the buckets in parallel matches the sequential expansion
+        let func = (log_domain, points);
+        let (key1, key2) = Cuckoo::<T>::gen(&func, &mut rng).unwrap();
+        let p1_results = Cuckoo::<T>::full_eval(&key1).unwrap();
+        let p2_results = Cuckoo::<T>::full_eval(&key2).unwrap();
+        assert!(Cuckoo::<T>::par_full_eval(&key1).unwrap() == p1_results);
+        assert!(Cuckoo::<T>::par_full_eval(&key2).unwrap() == p2_results);
+    }
+}
+
+#[test]
+fn test_correctness() {
+    super::tests::test_correctness_helper::<F, BGI16>();
+    super::tests::test_correctness_helper::<F, AesBGI16>();
+}
+
+#[test]
+fn test_ring_correctness() {
+    super::tests::test_correctness_helper::<R, RingBGI16>();
+}
+
+#[test]
+fn test_bad_inputs() {
+    super::tests::test_bad_inputs_helper::<BGI16>();
+}
+
+#[test]
+fn test_full_eval() {
+    super::tests::test_full_eval_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_eval() {
+    super::tests::test_parallel_eval_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[test]
+fn test_empty_bucket_masks() {
+    let mut rng = test_rng();
+
+    // A single point leaves all but one of the buckets empty, and every bucket's key has a
+    // random mask, so empty buckets can't be told apart from the one holding the point
+    let func = (20, sample_points::<F>(20, 1));
+    let (key1, key2) = BGI16::gen(&func, &mut rng).unwrap();
+    assert_eq!(key1.keys.len(), 3 * 256);
+    for (k1, k2) in key1.keys.iter().zip(&key2.keys) {
+        assert!(k1.mask.is_some_and(|mask| mask != F::zero()));
+        assert!(k1.mask == k2.mask);
+    }
+}
+
+#[test]
+fn test_large_domain() {
+    let mut rng = test_rng();
+
+    // Points may be at either end of the largest domain indexed by `usize`
+    let mut points = sample_points::<F>(16, 20);
+    points.extend([
+        (usize::MAX, F::one()),
+        (usize::MAX - 1, F::one() + F::one()),
+    ]);
+    let (key1, key2) = BGI16::gen(&(64, points.clone()), &mut rng).unwrap();
+    for p in points
+        .iter()
+        .map(|(x, _)| *x)
+        .chain([1 << 63, usize::MAX - 2])
+    {
+        let p1_result = BGI16::eval(&key1, &p).unwrap();
+        let p2_result = BGI16::eval(&key2, &p).unwrap();
+        assert!(BGI16::decode((&p1_result, &p2_result)).unwrap() == expected(&points, p));
+    }
+}
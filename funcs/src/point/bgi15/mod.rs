@@ -1,24 +1,24 @@
 use ark_ff::Field;
 use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
-use rand::{CryptoRng, Rng, RngCore, SeedableRng};
+use rand::{CryptoRng, Rng, RngCore};
 use std::{error::Error, marker::PhantomData, vec::Vec};
 
 use crate::{
     point::DPF,
     tree::{TreeFSS, TreeScheme},
-    Pair, Seed,
+    AbelianGroup, Expansion, Pair, Seed, TreeDomain, TreePrg,
 };
 
 /// DPF scheme based on [[BGI15]].
 ///
 /// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
-pub type Bgi15DPF<F, PRG, S> = TreeScheme<F, PRG, S, Bgi15<F, PRG, S>>;
+pub type Bgi15DPF<F, P, D = usize> = TreeScheme<F, P, Bgi15<F, P, D>>;
 
-impl<F, PRG, S> DPF<F> for Bgi15DPF<F, PRG, S>
+impl<F, P, D> DPF<F, D> for Bgi15DPF<F, P, D>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
+    F: Field + AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
 {
 }
 
@@ -29,6 +29,43 @@ pub struct Node<S: Seed> {
     pub control_bits: Pair<bool>,
 }
 
+impl<S: Seed> Node<S> {
+    /// Samples the parties' root nodes s.t. their children along `bit` have independent seeds and
+    /// different control-bits, and their children along `!bit` are identical.
+    pub fn gen_roots<RNG: CryptoRng + RngCore>(bit: bool, rng: &mut RNG) -> (Self, Self) {
+        // Sample party 1's initial 0/1 seeds and control bits.
+        let mut p1_seeds = Pair::<S>::default();
+        rng.fill_bytes(p1_seeds[0].as_mut());
+        rng.fill_bytes(p1_seeds[1].as_mut());
+
+        let mut p1_control_bits = Pair::<bool>::default();
+        p1_control_bits[0] = rng.gen_bool(0.5);
+        p1_control_bits[1] = rng.gen_bool(0.5);
+
+        // Sample party 2's initial seeds and control bits. The seed for the bit corresponding to
+        // `!bit` will be the same as party 1, the control bit for `bit` will be different from
+        // party 1, and the control bit for `!bit` will be the same as party 1.
+        let mut p2_seeds = Pair::<S>::default();
+        rng.fill_bytes(p2_seeds[bit].as_mut());
+        p2_seeds[!bit] = p1_seeds[!bit];
+
+        let mut p2_control_bits = Pair::<bool>::default();
+        p2_control_bits[bit] = !p1_control_bits[bit];
+        p2_control_bits[!bit] = p1_control_bits[!bit];
+
+        (
+            Node {
+                seeds: p1_seeds,
+                control_bits: p1_control_bits,
+            },
+            Node {
+                seeds: p2_seeds,
+                control_bits: p2_control_bits,
+            },
+        )
+    }
+}
+
 /// `CodeWord`s have the same structure as a `Node` but they are masking values, not the actual
 /// seed/control-bit values.
 pub type CodeWord<S> = Node<S>;
@@ -53,32 +90,33 @@ impl<S: Seed> IntermediateNode<S> {
     }
 }
 
-pub struct Bgi15<F, PRG, S>
+pub struct Bgi15<F, P, D = usize>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
+    F: Field + AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
 {
     _field: PhantomData<F>,
-    _prg: PhantomData<PRG>,
-    _seed: PhantomData<S>,
+    _prg: PhantomData<P>,
+    _domain: PhantomData<D>,
 }
 
-impl<F, PRG, S> TreeFSS<F, PRG, S> for Bgi15<F, PRG, S>
+impl<F, P, D> TreeFSS<F, P> for Bgi15<F, P, D>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
+    F: Field + AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
 {
-    type Root = Node<S>;
-    type Codeword = Pair<CodeWord<S>>;
-    type Description = super::PFDescription<F>;
-    type Node = Node<S>;
-    type EvaluationNode = IntermediateNode<S>;
+    type Root = Node<P::Seed>;
+    type Codeword = Pair<CodeWord<P::Seed>>;
+    type Description = super::PFDescription<F, D>;
+    type Domain = D;
+    type Node = Node<P::Seed>;
+    type EvaluationNode = IntermediateNode<P::Seed>;
 
     fn get_domain_and_point(f: &Self::Description) -> Result<(usize, Vec<bool>), Box<dyn Error>> {
         let log_domain = f.0;
-        let x = crate::usize_to_bits(log_domain, f.1)?;
+        let x = f.1.to_bits(log_domain)?;
         Ok((log_domain, x))
     }
 
@@ -87,36 +125,7 @@ where
         bit: bool,
         rng: &mut RNG,
     ) -> (Self::Root, Self::Root) {
-        // Sample party 1's initial 0/1 seeds and control bits.
-        let mut p1_seeds = Pair::<S>::default();
-        rng.fill_bytes(p1_seeds[0].as_mut());
-        rng.fill_bytes(p1_seeds[1].as_mut());
-
-        let mut p1_control_bits = Pair::<bool>::default();
-        p1_control_bits[0] = rng.gen_bool(0.5);
-        p1_control_bits[1] = rng.gen_bool(0.5);
-
-        // Sample party 2's initial seeds and control bits. The seed for the bit corresponding to
-        // `!bit` will be the same as party 1, the control bit for `bit` will be different from
-        // party 1, and the control bit for `!bit` will be the same as party 1.
-        let mut p2_seeds = Pair::<S>::default();
-        rng.fill_bytes(p2_seeds[bit].as_mut());
-        p2_seeds[!bit] = p1_seeds[!bit];
-
-        let mut p2_control_bits = Pair::<bool>::default();
-        p2_control_bits[bit] = !p1_control_bits[bit];
-        p2_control_bits[!bit] = p1_control_bits[!bit];
-
-        (
-            Node {
-                seeds: p1_seeds,
-                control_bits: p1_control_bits,
-            },
-            Node {
-                seeds: p2_seeds,
-                control_bits: p2_control_bits,
-            },
-        )
+        Node::gen_roots(bit, rng)
     }
 
     fn evaluate_root(bit: bool, root: &Self::Root) -> (Self::EvaluationNode, Option<F>) {
@@ -126,18 +135,13 @@ where
         )
     }
 
-    fn sample_masked_level(node: &Self::EvaluationNode) -> Self::Node {
-        let mut prg = PRG::from_seed(node.seed);
-
-        // Sample masked seeds.
-        let mut masked_seeds = Pair::<S>::default();
-        prg.fill_bytes(masked_seeds[0].as_mut());
-        prg.fill_bytes(masked_seeds[1].as_mut());
+    fn seed(node: &Self::EvaluationNode) -> &P::Seed {
+        &node.seed
+    }
 
-        // Sample masked control-bits
-        let mut masked_control_bits = Pair::<bool>::default();
-        masked_control_bits[0] = prg.gen_bool(0.5);
-        masked_control_bits[1] = prg.gen_bool(0.5);
+    fn sample_masked_level(_: &Self::EvaluationNode, expansion: Expansion<P>) -> Self::Node {
+        // The expansion of the seed holds the masked seeds and control-bits
+        let (masked_seeds, masked_control_bits, _) = expansion;
 
         Self::Node {
             seeds: masked_seeds,
@@ -151,6 +155,7 @@ where
         _: &Self::EvaluationNode,
         p1_masked_node: &Self::Node,
         p2_masked_node: &Self::Node,
+        _: Option<F>,
         rng: &mut RNG,
     ) -> Self::Codeword {
         // For each level of the tree, there are two `CodeWords`, each corresponding to the
@@ -161,9 +166,9 @@ where
         // corresponding to `point`, then the subsequent `Node` will be randomly sampled.
         // However, if the path ever diverges from `point`, then these masks will produce an
         // identical `Node` for both parties.
-        let mut codeword_0_seeds = Pair::<S>::default();
+        let mut codeword_0_seeds = Pair::<P::Seed>::default();
         let mut codeword_0_control_bits = Pair::<bool>::default();
-        let mut codeword_1_seeds = Pair::<S>::default();
+        let mut codeword_1_seeds = Pair::<P::Seed>::default();
         let mut codeword_1_control_bits = Pair::<bool>::default();
 
         // The seed masks corresponding to `point` are sampled randomly
@@ -242,21 +247,16 @@ where
         }
     }
 
-    #[inline]
-    fn compute_output_elem(node: &Self::EvaluationNode) -> Option<F> {
-        // Using the PRG seed, sample a random field element
-        Some(F::rand(&mut PRG::from_seed(node.seed)))
-    }
-
     #[inline]
     fn compute_mask(
         f: &Self::Description,
-        p1_output_elem: &Option<F>,
-        p2_output_elem: &Option<F>,
+        p1_node: &Self::EvaluationNode,
+        p2_node: &Self::EvaluationNode,
+        _: Option<F>,
     ) -> Result<Option<F>, Box<dyn Error>> {
         let (_, _, val) = *f;
-        let p1_elem = p1_output_elem.ok_or("Gen(): Output element is None")?;
-        let p2_elem = p2_output_elem.ok_or("Gen(): Output element is None")?;
+        let p1_elem = Self::output_elem(p1_node);
+        let p2_elem = Self::output_elem(p2_node);
         // Output a mask s.t. both parties hold additive secret shares of `val`
         match p1_elem == p2_elem {
             true => {
@@ -271,4 +271,27 @@ where
             )),
         }
     }
+
+    #[inline]
+    fn compute_output(
+        node: &Self::EvaluationNode,
+        mask: Option<&F>,
+        _: Option<F>,
+    ) -> Result<F, Box<dyn Error>> {
+        let mask = mask.ok_or("Eval(): Key has no output mask")?;
+        Ok(Self::output_elem(node) * mask)
+    }
+}
+
+impl<F, P, D> Bgi15<F, P, D>
+where
+    F: Field + AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+    /// Using the PRG seed of a leaf, sample a random field element
+    #[inline]
+    fn output_elem(node: &IntermediateNode<P::Seed>) -> F {
+        F::rand(&mut P::material(&node.seed))
+    }
 }
@@ -0,0 +1,224 @@
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, vec::Vec};
+
+use crate::{
+    point::{
+        bgi15::{IntermediateNode, Node},
+        HidesZero, DPF,
+    },
+    tree::{TreeFSS, TreeScheme},
+    AbelianGroup, Expansion, Pair, Seed, TreeDomain, TreePrg,
+};
+
+/// DPF scheme based on [[BGI16]].
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub type Bgi16DPF<F, P, D = usize> = TreeScheme<F, P, Bgi16<F, P, D>>;
+
+/// DPF scheme based on [[BGI16]] whose value at the special point is a vector of `M` group
+/// elements. Each party expands its leaf seed into `M` elements, so the key has a single tree
+/// and an `M`-element output mask.
+///
+/// The length `M` is fixed at compile time, since `AbelianGroup::group_zero` and
+/// `AbelianGroup::group_sample` have no way of learning a length at runtime. Payloads whose
+/// length is only known at runtime must be padded to a fixed maximum length `M`.
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub type Bgi16VectorDPF<F, P, const M: usize> = Bgi16DPF<[F; M], P>;
+
+impl<F, P, D> DPF<F, D> for Bgi16DPF<F, P, D>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+}
+
+// The output mask is the value minus the difference of the parties' pseudorandom leaf outputs,
+// so it is uniformly random whether or not the value is zero
+impl<F, P, D> HidesZero<F, D> for Bgi16DPF<F, P, D>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+}
+
+/// A `CodeWord` corrects the children of a node whose control-bit is set. It contains a single
+/// seed, which is applied to whichever child is being evaluated, and a control-bit for each
+/// child.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CodeWord<S: Seed> {
+    pub seed: S,
+    pub control_bits: Pair<bool>,
+}
+
+pub struct Bgi16<F, P, D = usize>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+    _field: PhantomData<F>,
+    _prg: PhantomData<P>,
+    _domain: PhantomData<D>,
+}
+
+impl<F, P, D> TreeFSS<F, P> for Bgi16<F, P, D>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+    type Root = Node<P::Seed>;
+    type Codeword = CodeWord<P::Seed>;
+    type Description = super::PFDescription<F, D>;
+    type Domain = D;
+    type Node = Node<P::Seed>;
+    type EvaluationNode = IntermediateNode<P::Seed>;
+
+    fn get_domain_and_point(f: &Self::Description) -> Result<(usize, Vec<bool>), Box<dyn Error>> {
+        let log_domain = f.0;
+        let x = f.1.to_bits(log_domain)?;
+        Ok((log_domain, x))
+    }
+
+    fn gen_root<RNG: CryptoRng + RngCore>(
+        _: &Self::Description,
+        bit: bool,
+        rng: &mut RNG,
+    ) -> (Self::Root, Self::Root) {
+        // The root nodes need to satisfy the same invariant as the first level of [BGI15]: the
+        // parties' children along `bit` have independent seeds and different control-bits, and
+        // their children along `!bit` are identical.
+        Node::gen_roots(bit, rng)
+    }
+
+    fn evaluate_root(bit: bool, root: &Self::Root) -> (Self::EvaluationNode, Option<F>) {
+        (
+            IntermediateNode::new(bit, &root.seeds, &root.control_bits),
+            None,
+        )
+    }
+
+    fn seed(node: &Self::EvaluationNode) -> &P::Seed {
+        &node.seed
+    }
+
+    fn sample_masked_level(_: &Self::EvaluationNode, expansion: Expansion<P>) -> Self::Node {
+        // The expansion of the seed holds the masked seeds and control-bits
+        let (masked_seeds, masked_control_bits, _) = expansion;
+
+        Self::Node {
+            seeds: masked_seeds,
+            control_bits: masked_control_bits,
+        }
+    }
+
+    fn compute_codeword<RNG: CryptoRng + RngCore>(
+        _: &Self::Description,
+        bit: bool,
+        _: &Self::EvaluationNode,
+        p1_masked_node: &Self::Node,
+        p2_masked_node: &Self::Node,
+        _: Option<F>,
+        _: &mut RNG,
+    ) -> Self::Codeword {
+        // Exactly one party's current control-bit is set when evaluating the path corresponding
+        // to `point`, so exactly one party applies the `CodeWord`. Otherwise the parties hold
+        // identical nodes, and will either both or neither apply it.
+        //
+        // The seed mask is the XOR of the parties' seeds corresponding to `!point`, so both
+        // parties hold the same seed after one of them applies it.
+        let mut codeword_seed = P::Seed::default();
+        codeword_seed
+            .as_mut()
+            .iter_mut()
+            .zip(p1_masked_node.seeds[!bit].as_ref())
+            .zip(p2_masked_node.seeds[!bit].as_ref())
+            .for_each(|((cs, p1_s), p2_s)| {
+                *cs = p1_s ^ p2_s;
+            });
+
+        // The control-bit masks ensure that the control-bits of the parties are different for
+        // `point` i.e. their XOR is true, and the same for `!point` i.e. their XOR is false
+        let mut codeword_control_bits = Pair::<bool>::default();
+        codeword_control_bits[bit] =
+            true ^ p1_masked_node.control_bits[bit] ^ p2_masked_node.control_bits[bit];
+        codeword_control_bits[!bit] =
+            false ^ p1_masked_node.control_bits[!bit] ^ p2_masked_node.control_bits[!bit];
+
+        CodeWord {
+            seed: codeword_seed,
+            control_bits: codeword_control_bits,
+        }
+    }
+
+    fn compute_next_level(
+        bit: bool,
+        node: &Self::EvaluationNode,
+        masked_node: Self::Node,
+        codeword: &Self::Codeword,
+        _: Option<&mut F>,
+    ) -> Self::EvaluationNode {
+        let mut next = IntermediateNode::new(bit, &masked_node.seeds, &masked_node.control_bits);
+
+        // Only apply the codeword if the current control-bit is set
+        if node.control_bit {
+            next.seed
+                .as_mut()
+                .iter_mut()
+                .zip(codeword.seed.as_ref())
+                .for_each(|(s, cs)| *s ^= cs);
+            next.control_bit ^= codeword.control_bits[bit];
+        }
+        next
+    }
+
+    #[inline]
+    fn compute_mask(
+        f: &Self::Description,
+        p1_node: &Self::EvaluationNode,
+        p2_node: &Self::EvaluationNode,
+        _: Option<F>,
+    ) -> Result<Option<F>, Box<dyn Error>> {
+        let (_, _, val) = *f;
+        let p1_elem = Self::output_elem(p1_node);
+        let p2_elem = Self::output_elem(p2_node);
+
+        // Output a mask s.t. the party whose final control-bit is set can correct the difference
+        // of the parties' group elements to `val`
+        let mask = val.group_sub(&p1_elem).group_add(&p2_elem);
+        match p2_node.control_bit {
+            true => Ok(Some(mask.group_neg())),
+            false => Ok(Some(mask)),
+        }
+    }
+
+    #[inline]
+    fn compute_output(
+        node: &Self::EvaluationNode,
+        mask: Option<&F>,
+        _: Option<F>,
+    ) -> Result<F, Box<dyn Error>> {
+        let mask = mask.ok_or("Eval(): Key has no output mask")?;
+        match node.control_bit {
+            true => Ok(Self::output_elem(node).group_add(mask)),
+            false => Ok(Self::output_elem(node)),
+        }
+    }
+}
+
+impl<F, P, D> Bgi16<F, P, D>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    D: TreeDomain,
+{
+    /// Using the PRG seed of a leaf, sample a random group element
+    #[inline]
+    fn output_elem(node: &IntermediateNode<P::Seed>) -> F {
+        F::group_sample(&mut P::material(&node.seed))
+    }
+}
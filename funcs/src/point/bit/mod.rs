@@ -0,0 +1,115 @@
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, vec::Vec};
+
+use crate::{
+    point::{
+        bgi15::{IntermediateNode, Node},
+        bgi16::{Bgi16, CodeWord},
+        DPF,
+    },
+    tree::{TreeFSS, TreeScheme},
+    Expansion, TreeDomain, TreePrg, Xor,
+};
+
+/// DPF scheme for the point function which is 1 at a single point, with shares in `Z_2`.
+pub type BitDPF<P, D = usize> = TreeScheme<Xor<bool>, P, Bit<P, D>>;
+
+impl<P: TreePrg, D: TreeDomain> DPF<Xor<bool>, D> for BitDPF<P, D> {}
+
+/// A bit-valued DPF using the tree of [[BGI16]].
+///
+/// The parties' final control-bits differ exactly at the special point, so they already form XOR
+/// shares of the point function with value 1. Each party outputs its control-bit directly: the
+/// key has no output mask and evaluation skips sampling an output element from the leaf seed.
+/// Since the value at the point is fixed, `gen` only accepts `Xor(true)` as the value.
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub struct Bit<P: TreePrg, D: TreeDomain = usize> {
+    _prg: PhantomData<P>,
+    _domain: PhantomData<D>,
+}
+
+impl<P: TreePrg, D: TreeDomain> TreeFSS<Xor<bool>, P> for Bit<P, D> {
+    type Root = Node<P::Seed>;
+    type Codeword = CodeWord<P::Seed>;
+    type Description = super::PFDescription<Xor<bool>, D>;
+    type Domain = D;
+    type Node = Node<P::Seed>;
+    type EvaluationNode = IntermediateNode<P::Seed>;
+
+    fn get_domain_and_point(f: &Self::Description) -> Result<(usize, Vec<bool>), Box<dyn Error>> {
+        if !f.2 .0 {
+            return Err("BitDPF(): The value at the point must be 1".into());
+        }
+        Bgi16::<Xor<bool>, P, D>::get_domain_and_point(f)
+    }
+
+    fn gen_root<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        bit: bool,
+        rng: &mut RNG,
+    ) -> (Self::Root, Self::Root) {
+        Bgi16::<Xor<bool>, P, D>::gen_root(f, bit, rng)
+    }
+
+    fn evaluate_root(bit: bool, root: &Self::Root) -> (Self::EvaluationNode, Option<Xor<bool>>) {
+        Bgi16::<Xor<bool>, P, D>::evaluate_root(bit, root)
+    }
+
+    fn seed(node: &Self::EvaluationNode) -> &P::Seed {
+        Bgi16::<Xor<bool>, P, D>::seed(node)
+    }
+
+    fn sample_masked_level(node: &Self::EvaluationNode, expansion: Expansion<P>) -> Self::Node {
+        Bgi16::<Xor<bool>, P, D>::sample_masked_level(node, expansion)
+    }
+
+    fn compute_codeword<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        bit: bool,
+        p1_node: &Self::EvaluationNode,
+        p1_masked_node: &Self::Node,
+        p2_masked_node: &Self::Node,
+        accumulated: Option<Xor<bool>>,
+        rng: &mut RNG,
+    ) -> Self::Codeword {
+        Bgi16::<Xor<bool>, P, D>::compute_codeword(
+            f,
+            bit,
+            p1_node,
+            p1_masked_node,
+            p2_masked_node,
+            accumulated,
+            rng,
+        )
+    }
+
+    fn compute_next_level(
+        bit: bool,
+        node: &Self::EvaluationNode,
+        masked_node: Self::Node,
+        codeword: &Self::Codeword,
+        accumulator: Option<&mut Xor<bool>>,
+    ) -> Self::EvaluationNode {
+        Bgi16::<Xor<bool>, P, D>::compute_next_level(bit, node, masked_node, codeword, accumulator)
+    }
+
+    #[inline]
+    fn compute_mask(
+        _: &Self::Description,
+        _: &Self::EvaluationNode,
+        _: &Self::EvaluationNode,
+        _: Option<Xor<bool>>,
+    ) -> Result<Option<Xor<bool>>, Box<dyn Error>> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn compute_output(
+        node: &Self::EvaluationNode,
+        _: Option<&Xor<bool>>,
+        _: Option<Xor<bool>>,
+    ) -> Result<Xor<bool>, Box<dyn Error>> {
+        Ok(Xor(node.control_bit))
+    }
+}
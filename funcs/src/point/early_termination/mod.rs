@@ -0,0 +1,284 @@
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, ops::Range, vec::Vec};
+
+use crate::{
+    domain::last_point,
+    point::{bgi16::Bgi16DPF, HidesZero, DPF},
+    tree::{TreeFSS, TreeKey, TreeScheme},
+    AbelianGroup, TreePrg, Xor, FSS,
+};
+
+/// DPF scheme based on [[BGI16]] whose tree stops `log2(M)` levels early, with each leaf
+/// expanding into `M` outputs.
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub type Bgi16EarlyTerminationDPF<G, P, const M: usize> =
+    EarlyTermination<G, [G; M], Bgi16DPF<[G; M], P>>;
+
+/// DPF scheme based on [[BGI16]] with bit outputs, whose tree stops `log2(128 W)` levels early.
+/// Each leaf packs the outputs of `128 W` consecutive points into `W` words, so the output mask
+/// takes one bit per point rather than one byte.
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub type Bgi16PackedBitDPF<P, const W: usize> =
+    EarlyTermination<Xor<bool>, [Xor<u128>; W], Bgi16DPF<[Xor<u128>; W], P>>;
+
+/// The outputs of the consecutive points covered by a leaf of an early-terminated tree.
+pub trait Leaf<G: AbelianGroup>: AbelianGroup {
+    /// The number of outputs in a leaf, which must be a power of two
+    const OUTPUTS: usize;
+
+    /// Outputs the leaf which is `val` at index `i`, and 0 everywhere else
+    fn singleton(i: usize, val: G) -> Self;
+
+    /// Outputs the element at index `i` of the leaf
+    fn output(&self, i: usize) -> G;
+}
+
+impl<G: AbelianGroup, const M: usize> Leaf<G> for [G; M]
+where
+    [G; M]: AbelianGroup,
+{
+    const OUTPUTS: usize = M;
+
+    #[inline]
+    fn singleton(i: usize, val: G) -> Self {
+        let mut leaf = [G::group_zero(); M];
+        leaf[i] = val;
+        leaf
+    }
+
+    #[inline]
+    fn output(&self, i: usize) -> G {
+        self[i]
+    }
+}
+
+/// Bits are packed into words, with output `i` being bit `i mod 128` of word `i / 128`.
+impl<const W: usize> Leaf<Xor<bool>> for [Xor<u128>; W]
+where
+    [Xor<u128>; W]: AbelianGroup,
+{
+    const OUTPUTS: usize = W * 128;
+
+    #[inline]
+    fn singleton(i: usize, val: Xor<bool>) -> Self {
+        let mut leaf = [Xor(0); W];
+        leaf[i / 128] = Xor((val.0 as u128) << (i % 128));
+        leaf
+    }
+
+    #[inline]
+    fn output(&self, i: usize) -> Xor<bool> {
+        Xor((self[i / 128].0 >> (i % 128)) & 1 == 1)
+    }
+}
+
+/// A DPF over the domain `{0, 1}^n` built from a DPF `S` with range `L` over the domain
+/// `{0, 1}^(n - ν)`, where each `L` holds `M = 2^ν` elements of `G`. The tree is `ν` levels
+/// shorter, and each of its leaves covers the `M` consecutive points sharing the leaf's prefix:
+/// the leaf at `x >> ν` holds the vector which is `y` at index `x mod M`, and 0 everywhere else.
+///
+/// This reduces the depth of the tree, and so the size of keys and the number of PRG calls per
+/// output, by `ν` levels. In exchange, the output mask is a vector of `M` elements, so this is
+/// most useful for small groups, e.g. bits or `Z_{2^16}`. Bits are best packed into words by
+/// `Bgi16PackedBitDPF`, since an array of `Xor<bool>` takes a byte per bit.
+pub struct EarlyTermination<G, L, S>
+where
+    G: AbelianGroup,
+    L: Leaf<G>,
+    S: DPF<L, Share = L>,
+{
+    _group: PhantomData<G>,
+    _leaf: PhantomData<L>,
+    _dpf: PhantomData<S>,
+}
+
+impl<G, L, S> DPF<G> for EarlyTermination<G, L, S>
+where
+    G: AbelianGroup,
+    L: Leaf<G>,
+    S: DPF<L, Share = L>,
+{
+}
+
+// A key is a key of `S` for a leaf, which is zero exactly when the value is zero
+impl<G, L, S> HidesZero<G> for EarlyTermination<G, L, S>
+where
+    G: AbelianGroup,
+    L: Leaf<G>,
+    S: HidesZero<L, Share = L>,
+{
+}
+
+impl<G, L, S> FSS for EarlyTermination<G, L, S>
+where
+    G: AbelianGroup,
+    L: Leaf<G>,
+    S: DPF<L, Share = L>,
+{
+    type Key = S::Key;
+    type Description = super::PFDescription<G>;
+    type Domain = usize;
+    type Range = G;
+    type Share = G;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        let (log_domain, x, val) = *f;
+        let nu = Self::log_outputs()?;
+        if log_domain <= nu {
+            return Err("EarlyTermination(): Domain must contain more than one leaf".into());
+        }
+
+        // The leaf containing `x` holds `val` at the position of `x` within it
+        let leaf_val = L::singleton(x % L::OUTPUTS, val);
+        S::gen(&(log_domain - nu, x >> nu, leaf_val), rng)
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<G, Box<dyn Error>> {
+        let leaf = S::eval(key, &(point >> Self::log_outputs()?))?;
+        Ok(leaf.output(point % L::OUTPUTS))
+    }
+
+    fn decode(shares: (&G, &G)) -> Result<G, Box<dyn Error>> {
+        Ok(shares.0.group_sub(shares.1))
+    }
+}
+
+impl<G, L, S> EarlyTermination<G, L, S>
+where
+    G: AbelianGroup,
+    L: Leaf<G>,
+    S: DPF<L, Share = L>,
+{
+    /// The number of levels `ν` the tree stops early, i.e. `log2(M)`
+    #[inline]
+    fn log_outputs() -> Result<usize, Box<dyn Error>> {
+        match L::OUTPUTS.is_power_of_two() {
+            true => Ok(L::OUTPUTS.trailing_zeros() as usize),
+            false => Err("EarlyTermination(): Outputs per leaf must be a power of two".into()),
+        }
+    }
+}
+
+impl<G, L, P, T> EarlyTermination<G, L, TreeScheme<L, P, T>>
+where
+    G: AbelianGroup,
+    L: Leaf<G>,
+    P: TreePrg,
+    T: TreeFSS<L, P, Domain = usize>,
+    TreeScheme<L, P, T>: DPF<L, Key = TreeKey<L, P, T>, Share = L>,
+{
+    /// Takes a `Key` as input, and outputs a secret share of the underlying function at every
+    /// point in the domain, ordered by point.
+    pub fn full_eval(key: &TreeKey<L, P, T>) -> Result<Vec<G>, Box<dyn Error>> {
+        Ok(Self::flatten(&TreeScheme::<L, P, T>::full_eval(key)?).collect())
+    }
+
+    /// Takes a `Key` and a range of points as input, and outputs a secret share of the underlying
+    /// function at each point in `range`, ordered by point.
+    pub fn eval_range(
+        key: &TreeKey<L, P, T>,
+        range: Range<usize>,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        let leaves = Self::leaf_range(key, &range)?;
+        let shares = TreeScheme::<L, P, T>::eval_range(key, leaves.clone())?;
+        Ok(Self::trim(shares, leaves, range))
+    }
+
+    /// Takes a `Key` and a batch of points as input, and outputs a secret share of the underlying
+    /// function at each point, in the same order as `points`.
+    pub fn batch_eval(key: &TreeKey<L, P, T>, points: &[usize]) -> Result<Vec<G>, Box<dyn Error>> {
+        let nu = Self::log_outputs()?;
+        let leaves = points.iter().map(|p| p >> nu).collect::<Vec<_>>();
+        let shares = TreeScheme::<L, P, T>::batch_eval(key, &leaves)?;
+        Ok(Self::select(shares, points))
+    }
+
+    /// Ensures that `range` is valid in the domain of `key`, and outputs the range of leaves
+    /// which cover it.
+    fn leaf_range(
+        key: &TreeKey<L, P, T>,
+        range: &Range<usize>,
+    ) -> Result<Range<usize>, Box<dyn Error>> {
+        let nu = Self::log_outputs()?;
+        let last = last_point(key.log_domain + nu)?;
+        if range.start > range.end || range.end.saturating_sub(1) > last {
+            return Err("Input range is not contained in provided domain".into());
+        }
+        Ok((range.start >> nu)..range.end.div_ceil(L::OUTPUTS))
+    }
+
+    /// Flattens the outputs of the leaves in `shares`, in order
+    fn flatten(shares: &[L]) -> impl Iterator<Item = G> + '_ {
+        shares
+            .iter()
+            .flat_map(|leaf| (0..L::OUTPUTS).map(move |i| leaf.output(i)))
+    }
+
+    /// Flattens the outputs of the leaves in `leaves`, and keeps those of the points in `range`
+    fn trim(shares: Vec<L>, leaves: Range<usize>, range: Range<usize>) -> Vec<G> {
+        let start = range.start - leaves.start * L::OUTPUTS;
+        Self::flatten(&shares)
+            .skip(start)
+            .take(range.len())
+            .collect()
+    }
+
+    /// Selects the output of each of `points` from the outputs of the leaves containing them
+    fn select(shares: Vec<L>, points: &[usize]) -> Vec<G> {
+        shares
+            .iter()
+            .zip(points)
+            .map(|(leaf, p)| leaf.output(p % L::OUTPUTS))
+            .collect()
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<G, L, P, T> EarlyTermination<G, L, TreeScheme<L, P, T>>
+where
+    G: AbelianGroup,
+    L: Leaf<G>,
+    P: TreePrg,
+    T: TreeFSS<L, P, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+    TreeScheme<L, P, T>: DPF<L, Key = TreeKey<L, P, T>, Share = L>,
+{
+    /// A multi-threaded version of `full_eval`. See `TreeScheme::par_full_eval`.
+    pub fn par_full_eval(
+        key: &TreeKey<L, P, T>,
+        split_depth: usize,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        let shares = TreeScheme::<L, P, T>::par_full_eval(key, split_depth)?;
+        Ok(Self::flatten(&shares).collect())
+    }
+
+    /// A multi-threaded version of `eval_range`. See `TreeScheme::par_eval_range`.
+    pub fn par_eval_range(
+        key: &TreeKey<L, P, T>,
+        range: Range<usize>,
+        split_depth: usize,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        let leaves = Self::leaf_range(key, &range)?;
+        let shares = TreeScheme::<L, P, T>::par_eval_range(key, leaves.clone(), split_depth)?;
+        Ok(Self::trim(shares, leaves, range))
+    }
+
+    /// A multi-threaded version of `batch_eval`. See `TreeScheme::par_batch_eval`.
+    pub fn par_batch_eval(
+        key: &TreeKey<L, P, T>,
+        points: &[usize],
+        split_depth: usize,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        let nu = Self::log_outputs()?;
+        let leaves = points.iter().map(|p| p >> nu).collect::<Vec<_>>();
+        let shares = TreeScheme::<L, P, T>::par_batch_eval(key, &leaves, split_depth)?;
+        Ok(Self::select(shares, points))
+    }
+}
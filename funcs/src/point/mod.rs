@@ -1,7 +1,6 @@
 //! A module implementing various distributed point function schemes
-use ark_ff::Field;
 
-use crate::FSS;
+use crate::{AbelianGroup, FSS};
 
 #[cfg(test)]
 pub(crate) mod tests;
@@ -11,7 +10,23 @@ pub(crate) mod tests;
 /// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
 pub mod bgi15;
 
-/// The domain of a point function
+/// DPF scheme based on [[BGI16]], with a single seed correction word per level of the tree.
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub mod bgi16;
+
+/// DPF scheme for bit-valued point functions with XOR shares, which outputs the control-bits of
+/// the [[BGI16]] tree directly.
+///
+/// [BGI16]: https://eprint.iacr.org/2018/707.pdf
+pub mod bit;
+
+/// DPF scheme whose tree stops early, with each leaf expanding into a vector of outputs for
+/// consecutive points.
+pub mod early_termination;
+
+/// The default domain of a point function. Schemes can also be defined over other `TreeDomain`s,
+/// e.g. `u128` or `BitString`.
 type PFDomain = usize;
 
 /// The range of a point function
@@ -19,10 +34,20 @@ type PFRange<F> = F;
 
 /// The description of a point function: the logarithm of the domain size, a
 /// point in that domain, and the value of that point.
-type PFDescription<F> = (usize, usize, F);
+pub(crate) type PFDescription<F, D = PFDomain> = (usize, D, F);
 
-/// A distributed point function (DPF) is a type of FSS scheme for point functions.
-pub trait DPF<F: Field>:
-    FSS<Domain = PFDomain, Range = PFRange<F>, Description = PFDescription<F>>
+/// A distributed point function (DPF) is a type of FSS scheme for point functions over the domain
+/// `D`.
+pub trait DPF<F: AbelianGroup, D = PFDomain>:
+    FSS<Domain = D, Range = PFRange<F>, Description = PFDescription<F, D>>
 {
 }
+
+/// A DPF whose keys hide whether the value of the point function is zero, i.e. each party's key
+/// for the zero function is distributed identically to its key for any other point function.
+///
+/// This doesn't hold for every DPF, e.g. the output mask of [[BGI15]] is zero exactly when the
+/// value is zero, so schemes which share the zero function must require this trait.
+///
+/// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
+pub trait HidesZero<F: AbelianGroup, D = PFDomain>: DPF<F, D> {}
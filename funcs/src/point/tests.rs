@@ -1,54 +1,57 @@
-use ark_ff::{
-    BigInteger64 as BigInteger, FftParameters, Fp64, Fp64Parameters, FpParameters, One,
-    UniformRand, Zero,
-};
+use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::test_rng;
 use rand::Rng;
 use rand_chacha::ChaChaRng;
 
-use crate::point::{bgi15, DPF};
+use crate::{
+    point::{
+        bgi15, bgi16, bit,
+        early_termination::{self, Leaf},
+        PFDescription, DPF,
+    },
+    test_field::F,
+    tree::{TreeFSS, TreeKey, TreeScheme},
+    AbelianGroup, BitString, FixedKeyAes, TreePrg, Xor, Z2k, FSS,
+};
 
-// Set field, seed, and PRG types
-type F = Fp64<FParameters>;
-type S = [u8; 32];
+// Set PRG type
 type PRG = ChaChaRng;
 
 // Aliases for various DPF types
-type BGI15 = bgi15::Bgi15DPF<F, PRG, S>;
-
-// Define a field to use. This is the same 63-bit field used in
-// "Lightweight Techniques for Private Heavy Hitters"
-struct FParameters;
-
-impl Fp64Parameters for FParameters {}
-impl FftParameters for FParameters {
-    type BigInt = BigInteger;
-    const TWO_ADICITY: u32 = 1;
-    const TWO_ADIC_ROOT_OF_UNITY: Self::BigInt = BigInteger([1]);
-}
-
-impl FpParameters for FParameters {
-    const MODULUS: BigInteger = BigInteger([9223372036854775783]);
-    const MODULUS_BITS: u32 = 63u32;
-    const REPR_SHAVE_BITS: u32 = 1;
-    const R: BigInteger = BigInteger([50]);
-    const R2: BigInteger = BigInteger([2500]);
-    const INV: u64 = 1106804644422573097;
-    const GENERATOR: BigInteger = BigInteger([3]);
-    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
-    const T: BigInteger = BigInteger([4611686018427387891]);
-    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([2305843009213693945]);
-    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([4611686018427387891]);
-}
-
-fn test_correctness_helper<D: DPF<F>>() {
+type BGI15 = bgi15::Bgi15DPF<F, PRG>;
+type AesBGI15 = bgi15::Bgi15DPF<F, FixedKeyAes>;
+type BGI16 = bgi16::Bgi16DPF<F, PRG>;
+type AesBGI16 = bgi16::Bgi16DPF<F, FixedKeyAes>;
+
+// Aliases for DPF types over the ring `Z_{2^64}`
+type R = Z2k<u64>;
+type RingBGI16 = bgi16::Bgi16DPF<R, PRG>;
+
+// Aliases for DPF types with XOR shares
+type XorBGI16 = bgi16::Bgi16DPF<[Xor<u8>; 16], PRG>;
+type BitDPF = bit::BitDPF<PRG>;
+
+// Alias for a DPF with a 1 KB payload
+type VectorBGI16 = bgi16::Bgi16VectorDPF<F, PRG, 128>;
+
+// Alias for a DPF whose tree stops a level early
+type EarlyBGI16 = early_termination::Bgi16EarlyTerminationDPF<F, PRG, 2>;
+
+// Aliases for DPF types over domains larger than `usize`
+type LargeBGI15 = bgi15::Bgi15DPF<F, PRG, u128>;
+type LargeBGI16 = bgi16::Bgi16DPF<F, PRG, u128>;
+type BitStringBGI15 = bgi15::Bgi15DPF<F, PRG, BitString>;
+type BitStringBGI16 = bgi16::Bgi16DPF<F, PRG, BitString>;
+
+fn test_correctness_helper<G: AbelianGroup, D: DPF<G>>() {
     let mut rng = test_rng();
 
     for log_domain in 2usize..12 {
         // Generate a random point in the given domain and field value
         let valid_range = 0..(1 << log_domain);
         let x = rng.gen_range(valid_range.clone());
-        let y = F::rand(&mut rng);
+        let y = G::group_sample(&mut rng);
 
         // Create the DPF
         let func = (log_domain, x, y);
@@ -61,7 +64,7 @@ fn test_correctness_helper<D: DPF<F>>() {
             if p == x {
                 assert!(D::decode((&p1_result, &p2_result)).unwrap() == y)
             } else {
-                assert!(D::decode((&p1_result, &p2_result)).unwrap() == F::zero())
+                assert!(D::decode((&p1_result, &p2_result)).unwrap() == G::group_zero())
             }
         }
     }
@@ -92,12 +95,577 @@ fn test_bad_inputs_helper<D: DPF<F>>() {
     assert!(result.is_err());
 }
 
+fn test_early_termination_helper<G: AbelianGroup, L: Leaf<G>>() {
+    type Early<G, L> = early_termination::EarlyTermination<G, L, bgi16::Bgi16DPF<L, PRG>>;
+    let mut rng = test_rng();
+    let nu = L::OUTPUTS.trailing_zeros() as usize;
+
+    for log_domain in (nu + 1)..(nu + 6) {
+        // Generate a random point in the given domain and group value
+        let x = rng.gen_range(0..(1 << log_domain));
+        let y = G::group_sample(&mut rng);
+
+        // Create the DPF. The tree stops `nu` levels early.
+        let (key1, key2) = Early::<G, L>::gen(&(log_domain, x, y), &mut rng).unwrap();
+        assert_eq!(key1.log_domain, log_domain - nu);
+        assert_eq!(key1.codewords.len(), log_domain - nu - 1);
+
+        // Full-domain evaluation agrees with evaluating each point
+        let p1_results = Early::<G, L>::full_eval(&key1).unwrap();
+        let p2_results = Early::<G, L>::full_eval(&key2).unwrap();
+        assert_eq!(p1_results.len(), 1 << log_domain);
+        for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+            assert_eq!(*p1_result, Early::<G, L>::eval(&key1, &p).unwrap());
+            let expected = if p == x { y } else { G::group_zero() };
+            assert_eq!(
+                Early::<G, L>::decode((p1_result, p2_result)).unwrap(),
+                expected
+            );
+        }
+
+        // Ranges and batches which don't line up with the leaves are trimmed
+        let start = rng.gen_range(0..(1 << log_domain));
+        let end = rng.gen_range(start..=(1 << log_domain));
+        let range_results = Early::<G, L>::eval_range(&key1, start..end).unwrap();
+        assert_eq!(range_results, p1_results[start..end]);
+
+        let points = (0..20)
+            .map(|_| rng.gen_range(0..(1 << log_domain)))
+            .collect::<Vec<usize>>();
+        let batch_results = Early::<G, L>::batch_eval(&key1, &points).unwrap();
+        for (p, result) in points.iter().zip(batch_results) {
+            assert_eq!(result, p1_results[*p]);
+        }
+
+        #[cfg(feature = "parallel")]
+        assert_eq!(Early::<G, L>::par_full_eval(&key1, 2).unwrap(), p1_results);
+
+        // Points outside the domain are rejected
+        assert!(Early::<G, L>::eval(&key1, &(1 << log_domain)).is_err());
+        assert!(Early::<G, L>::eval_range(&key1, 0..(1 << log_domain) + 1).is_err());
+    }
+
+    // The tree must have at least one level
+    assert!(Early::<G, L>::gen(&(nu, 0, G::group_zero()), &mut rng).is_err());
+
+    // Ranges may end at the last point of the largest domain indexed by `usize`
+    let x = usize::MAX - rng.gen_range(0..100);
+    let (key1, _) = Early::<G, L>::gen(&(64, x, G::group_sample(&mut rng)), &mut rng).unwrap();
+    let range = usize::MAX - 300..usize::MAX;
+    let range_results = Early::<G, L>::eval_range(&key1, range.clone()).unwrap();
+    for (p, result) in range.zip(range_results) {
+        assert_eq!(result, Early::<G, L>::eval(&key1, &p).unwrap());
+    }
+}
+
+fn test_large_domain_helper<D: DPF<F, u128>>() {
+    let mut rng = test_rng();
+
+    for log_domain in [64usize, 100, 128] {
+        // Generate a random point in the given domain and field value
+        let x = rng.gen::<u128>() >> (128 - log_domain);
+        let y = F::rand(&mut rng);
+
+        // Create the DPF
+        let func = (log_domain, x, y);
+        let (key1, key2) = D::gen(&func, &mut rng).unwrap();
+
+        // Evaluate the DPF at the point, and at points which differ from it in a single bit
+        let eval = |p: u128| {
+            let p1_result = D::eval(&key1, &p).unwrap();
+            let p2_result = D::eval(&key2, &p).unwrap();
+            D::decode((&p1_result, &p2_result)).unwrap()
+        };
+        assert_eq!(eval(x), y);
+        for i in 0..log_domain {
+            assert_eq!(eval(x ^ (1 << i)), F::zero());
+        }
+
+        // Points outside the domain are rejected
+        if log_domain < 128 {
+            assert!(D::gen(&(log_domain, 1 << log_domain, y), &mut rng).is_err());
+            assert!(D::eval(&key1, &(1 << log_domain)).is_err());
+        }
+    }
+}
+
+fn test_bit_string_helper<D: DPF<F, BitString>>() {
+    let mut rng = test_rng();
+
+    for log_domain in [8usize, 13, 256] {
+        // Generate a random bit string of the given length and field value
+        let bytes = (0..log_domain.div_ceil(8))
+            .map(|_| rng.gen())
+            .collect::<Vec<u8>>();
+        let x = BitString::new(bytes.clone(), log_domain).unwrap();
+        let y = F::rand(&mut rng);
+
+        // Create the DPF
+        let func = (log_domain, x.clone(), y);
+        let (key1, key2) = D::gen(&func, &mut rng).unwrap();
+
+        // Evaluate the DPF at the point, and at points which differ from it in a single bit
+        let eval = |p: &BitString| {
+            let p1_result = D::eval(&key1, p).unwrap();
+            let p2_result = D::eval(&key2, p).unwrap();
+            D::decode((&p1_result, &p2_result)).unwrap()
+        };
+        assert_eq!(eval(&x), y);
+        for i in 0..log_domain {
+            let mut flipped = bytes.clone();
+            flipped[i / 8] ^= 0x80 >> (i % 8);
+            assert_eq!(
+                eval(&BitString::new(flipped, log_domain).unwrap()),
+                F::zero()
+            );
+        }
+
+        // Bit strings of a different length are rejected
+        let longer = BitString::new(vec![0; bytes.len() + 1], log_domain + 1).unwrap();
+        assert!(D::eval(&key1, &longer).is_err());
+    }
+}
+
+fn test_full_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize>,
+{
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..12 {
+        // Generate a random point in the given domain and field value
+        let x = rng.gen_range(0..(1 << log_domain));
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, x, y);
+        let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        // Expand the full domain and ensure it matches pointwise evaluation
+        let p1_results = TreeScheme::<F, PRG, T>::full_eval(&key1).unwrap();
+        let p2_results = TreeScheme::<F, PRG, T>::full_eval(&key2).unwrap();
+        assert_eq!(p1_results.len(), 1 << log_domain);
+        assert_eq!(p2_results.len(), 1 << log_domain);
+
+        for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+            assert!(*p1_result == TreeScheme::<F, PRG, T>::eval(&key1, &p).unwrap());
+            assert!(*p2_result == TreeScheme::<F, PRG, T>::eval(&key2, &p).unwrap());
+            let result = TreeScheme::<F, PRG, T>::decode((p1_result, p2_result)).unwrap();
+            if p == x {
+                assert!(result == y)
+            } else {
+                assert!(result == F::zero())
+            }
+        }
+    }
+}
+
+fn test_batch_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize>,
+{
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..12 {
+        // Generate a random point in the given domain and field value
+        let max = 1 << log_domain;
+        let x = rng.gen_range(0..max);
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, x, y);
+        let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        // Query a random, unsorted batch of points which includes `x` and some duplicates
+        let mut points: Vec<usize> = (0..max).map(|_| rng.gen_range(0..max)).collect();
+        points.push(x);
+        points.push(points[0]);
+
+        // Ensure that batch evaluation matches pointwise evaluation
+        let p1_results = TreeScheme::<F, PRG, T>::batch_eval(&key1, &points).unwrap();
+        let p2_results = TreeScheme::<F, PRG, T>::batch_eval(&key2, &points).unwrap();
+        assert_eq!(p1_results.len(), points.len());
+        assert_eq!(p2_results.len(), points.len());
+        for (i, p) in points.iter().enumerate() {
+            assert!(p1_results[i] == TreeScheme::<F, PRG, T>::eval(&key1, p).unwrap());
+            assert!(p2_results[i] == TreeScheme::<F, PRG, T>::eval(&key2, p).unwrap());
+        }
+
+        // An empty batch produces no shares, while a batch with a bad point fails
+        assert!(TreeScheme::<F, PRG, T>::batch_eval(&key1, &[])
+            .unwrap()
+            .is_empty());
+        points.push(max);
+        assert!(TreeScheme::<F, PRG, T>::batch_eval(&key1, &points).is_err());
+    }
+}
+
+fn test_eval_range_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize>,
+{
+    let mut rng = test_rng();
+
+    // Include a domain large enough for subtrees to be split before being expanded
+    for log_domain in (2usize..12).chain([15]) {
+        // Generate a random point in the given domain and field value
+        let max = 1 << log_domain;
+        let x = rng.gen_range(0..max);
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, x, y);
+        let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        // Evaluate a random range and ensure it matches pointwise evaluation
+        let start = rng.gen_range(0..=max);
+        let end = rng.gen_range(start..=max);
+        let p1_results = TreeScheme::<F, PRG, T>::eval_range(&key1, start..end).unwrap();
+        let p2_results = TreeScheme::<F, PRG, T>::eval_range(&key2, start..end).unwrap();
+        assert_eq!(p1_results.len(), end - start);
+        assert_eq!(p2_results.len(), end - start);
+
+        for (p, (p1_result, p2_result)) in (start..end).zip(p1_results.iter().zip(&p2_results)) {
+            assert!(*p1_result == TreeScheme::<F, PRG, T>::eval(&key1, &p).unwrap());
+            assert!(*p2_result == TreeScheme::<F, PRG, T>::eval(&key2, &p).unwrap());
+            let result = TreeScheme::<F, PRG, T>::decode((p1_result, p2_result)).unwrap();
+            if p == x {
+                assert!(result == y)
+            } else {
+                assert!(result == F::zero())
+            }
+        }
+
+        // Ranges which leave the domain fail
+        assert!(TreeScheme::<F, PRG, T>::eval_range(&key1, start..max + 1).is_err());
+    }
+}
+
+fn test_max_domain_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+{
+    let mut rng = test_rng();
+
+    // Use the largest domain indexed by `usize`, with a point near its end
+    let log_domain = usize::BITS as usize;
+    let x = usize::MAX - rng.gen_range(0..100);
+    let y = F::rand(&mut rng);
+    let func = (log_domain, x, y);
+    let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+    // Ranges at either end of the domain match pointwise evaluation
+    for range in [0..100, usize::MAX - 200..usize::MAX] {
+        let p1_results = TreeScheme::<F, PRG, T>::eval_range(&key1, range.clone()).unwrap();
+        let p2_results = TreeScheme::<F, PRG, T>::eval_range(&key2, range.clone()).unwrap();
+        assert_eq!(p1_results.len(), range.len());
+        for (p, (p1_result, p2_result)) in range.zip(p1_results.iter().zip(&p2_results)) {
+            assert!(*p1_result == TreeScheme::<F, PRG, T>::eval(&key1, &p).unwrap());
+            let result = TreeScheme::<F, PRG, T>::decode((p1_result, p2_result)).unwrap();
+            match p == x {
+                true => assert!(result == y),
+                false => assert!(result == F::zero()),
+            }
+        }
+    }
+
+    // Batches may include the last point of the domain
+    let points = [usize::MAX, x, 0, 1 << 63, (1 << 63) - 1, usize::MAX];
+    let batch = TreeScheme::<F, PRG, T>::batch_eval(&key1, &points).unwrap();
+    for (p, share) in points.iter().zip(&batch) {
+        assert!(*share == TreeScheme::<F, PRG, T>::eval(&key1, p).unwrap());
+    }
+
+    #[cfg(feature = "parallel")]
+    {
+        let range = usize::MAX - 200..usize::MAX;
+        let par_range = TreeScheme::<F, PRG, T>::par_eval_range(&key1, range.clone(), 4).unwrap();
+        assert!(par_range == TreeScheme::<F, PRG, T>::eval_range(&key1, range).unwrap());
+        let par_batch = TreeScheme::<F, PRG, T>::par_batch_eval(&key1, &points, 4).unwrap();
+        assert!(par_batch == batch);
+    }
+
+    // The full domain is too large to expand
+    assert!(TreeScheme::<F, PRG, T>::full_eval(&key1).is_err());
+}
+
+#[cfg(feature = "parallel")]
+fn test_parallel_eval_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+{
+    let mut rng = test_rng();
+
+    for log_domain in 2usize..12 {
+        // Generate a random point in the given domain and field value
+        let max = 1 << log_domain;
+        let x = rng.gen_range(0..max);
+        let y = F::rand(&mut rng);
+
+        // Create the keys
+        let func = (log_domain, x, y);
+        let (key, _) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+
+        // Ensure that the multi-threaded evaluations match the single-threaded ones, including
+        // when the split depth is out of bounds
+        let full = TreeScheme::<F, PRG, T>::full_eval(&key).unwrap();
+        let start = rng.gen_range(0..=max);
+        let end = rng.gen_range(start..=max);
+        let points: Vec<usize> = (0..max).map(|_| rng.gen_range(0..max)).collect();
+        let batch = TreeScheme::<F, PRG, T>::batch_eval(&key, &points).unwrap();
+
+        for split_depth in [0, 1, log_domain / 2, log_domain, log_domain + 1] {
+            let par_full = TreeScheme::<F, PRG, T>::par_full_eval(&key, split_depth).unwrap();
+            assert!(par_full == full);
+
+            let par_range =
+                TreeScheme::<F, PRG, T>::par_eval_range(&key, start..end, split_depth).unwrap();
+            assert!(par_range == full[start..end]);
+
+            let par_batch =
+                TreeScheme::<F, PRG, T>::par_batch_eval(&key, &points, split_depth).unwrap();
+            assert!(par_batch == batch);
+        }
+    }
+}
+
+fn test_key_ownership_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize> + 'static,
+    T::Root: Send + Sync,
+    T::Codeword: Send + Sync,
+{
+    fn assert_send_sync<K: Send + Sync>(_: &K) {}
+
+    let mut rng = test_rng();
+    let log_domain: usize = 10;
+    let x = rng.gen_range(0..(1 << log_domain));
+    let y = F::rand(&mut rng);
+
+    // Both keys initially share their codewords
+    let func = (log_domain, x, y);
+    let (key1, key2) = TreeScheme::<F, PRG, T>::gen(&func, &mut rng).unwrap();
+    assert!(!key1.is_owned() && !key2.is_owned());
+
+    // A deserialized key owns its codewords and evaluates identically
+    let mut serialized = vec![0; key1.serialized_size()];
+    key1.serialize(&mut serialized[..]).unwrap();
+    let recovered = TreeKey::<F, PRG, T>::deserialize(serialized.as_slice()).unwrap();
+    assert!(recovered.is_owned());
+    assert!(
+        TreeScheme::<F, PRG, T>::full_eval(&recovered).unwrap()
+            == TreeScheme::<F, PRG, T>::full_eval(&key1).unwrap()
+    );
+
+    // Once one key takes ownership, neither key is shared
+    let key1 = key1.into_owned();
+    assert!(key1.is_owned() && key2.is_owned());
+
+    // Evaluate each key on its own thread
+    let [p1_results, p2_results] = [key1, key2].map(|key| {
+        assert_send_sync(&key);
+        std::thread::spawn(move || TreeScheme::<F, PRG, T>::full_eval(&key).unwrap())
+            .join()
+            .unwrap()
+    });
+    for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+        let result = TreeScheme::<F, PRG, T>::decode((p1_result, p2_result)).unwrap();
+        if p == x {
+            assert!(result == y)
+        } else {
+            assert!(result == F::zero())
+        }
+    }
+}
+
+fn test_malformed_key_helper<T>()
+where
+    T: TreeFSS<F, PRG, Description = PFDescription<F>, Domain = usize>,
+{
+    let mut rng = test_rng();
+    let log_domain: usize = 10;
+    let x = rng.gen_range(0..(1 << log_domain));
+    let y = F::rand(&mut rng);
+    let (key, _) = TreeScheme::<F, PRG, T>::gen(&(log_domain, x, y), &mut rng).unwrap();
+
+    // Keys with an empty domain, or a codeword missing, are rejected rather than evaluated
+    let mut empty_domain = key.clone().into_owned();
+    empty_domain.log_domain = 0;
+    std::sync::Arc::make_mut(&mut empty_domain.codewords).clear();
+    let mut missing_codeword = key.into_owned();
+    std::sync::Arc::make_mut(&mut missing_codeword.codewords).pop();
+    for malformed in [empty_domain, missing_codeword] {
+        let mut serialized = vec![0; malformed.serialized_size()];
+        malformed.serialize(&mut serialized[..]).unwrap();
+        assert!(TreeKey::<F, PRG, T>::deserialize(serialized.as_slice()).is_err());
+
+        assert!(TreeScheme::<F, PRG, T>::eval(&malformed, &0).is_err());
+        assert!(TreeScheme::<F, PRG, T>::full_eval(&malformed).is_err());
+        assert!(TreeScheme::<F, PRG, T>::eval_range(&malformed, 0..1).is_err());
+        assert!(TreeScheme::<F, PRG, T>::batch_eval(&malformed, &[0]).is_err());
+    }
+}
+
 #[test]
 fn test_correctness() {
-    super::tests::test_correctness_helper::<BGI15>();
+    super::tests::test_correctness_helper::<F, BGI15>();
+    super::tests::test_correctness_helper::<F, AesBGI15>();
+    super::tests::test_correctness_helper::<F, BGI16>();
+    super::tests::test_correctness_helper::<F, AesBGI16>();
+}
+
+#[test]
+fn test_ring_correctness() {
+    super::tests::test_correctness_helper::<R, RingBGI16>();
+    super::tests::test_correctness_helper::<Z2k<u8>, bgi16::Bgi16DPF<Z2k<u8>, PRG>>();
+}
+
+#[test]
+fn test_xor_correctness() {
+    super::tests::test_correctness_helper::<Xor<u64>, bgi16::Bgi16DPF<Xor<u64>, PRG>>();
+    super::tests::test_correctness_helper::<[Xor<u8>; 16], XorBGI16>();
+
+    let mut rng = test_rng();
+    for log_domain in 2usize..12 {
+        let x = rng.gen_range(0..(1 << log_domain));
+        let (key1, key2) = BitDPF::gen(&(log_domain, x, Xor(true)), &mut rng).unwrap();
+
+        // Shares are the parties' control-bits, so the keys don't need an output mask
+        assert!(key1.mask.is_none() && key2.mask.is_none());
+
+        let p1_results = BitDPF::full_eval(&key1).unwrap();
+        let p2_results = BitDPF::full_eval(&key2).unwrap();
+        for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+            assert_eq!(*p1_result, BitDPF::eval(&key1, &p).unwrap());
+            let result = BitDPF::decode((p1_result, p2_result)).unwrap();
+            assert_eq!(result, Xor(p == x));
+        }
+    }
+
+    // The value at the point must be 1
+    assert!(BitDPF::gen(&(10, 0, Xor(false)), &mut rng).is_err());
+}
+
+#[test]
+fn test_vector_correctness() {
+    super::tests::test_correctness_helper::<[F; 128], VectorBGI16>();
+    super::tests::test_correctness_helper::<[Z2k<u32>; 40], bgi16::Bgi16VectorDPF<Z2k<u32>, PRG, 40>>(
+    );
+
+    // The key has the same tree as a DPF with a single element, and a single vector mask
+    let mut rng = test_rng();
+    let log_domain = 20;
+    let x = rng.gen_range(0..(1 << log_domain));
+    let (key, _) = BGI16::gen(&(log_domain, x, F::rand(&mut rng)), &mut rng).unwrap();
+    let y = [(); 128].map(|_| F::rand(&mut rng));
+    let (vector_key, _) = VectorBGI16::gen(&(log_domain, x, y), &mut rng).unwrap();
+    assert_eq!(
+        vector_key.serialized_size(),
+        key.serialized_size() + 127 * F::zero().serialized_size()
+    );
+}
+
+#[test]
+fn test_early_termination_correctness() {
+    super::tests::test_correctness_helper::<F, EarlyBGI16>();
+    super::tests::test_early_termination_helper::<F, [F; 4]>();
+    super::tests::test_early_termination_helper::<Xor<bool>, [Xor<bool>; 128]>();
+    super::tests::test_early_termination_helper::<Xor<bool>, [Xor<u128>; 2]>();
+    super::tests::test_early_termination_helper::<Z2k<u16>, [Z2k<u16>; 16]>();
+}
+
+#[test]
+fn test_large_domain_correctness() {
+    super::tests::test_large_domain_helper::<LargeBGI15>();
+    super::tests::test_large_domain_helper::<LargeBGI16>();
+}
+
+#[test]
+fn test_bit_string_correctness() {
+    super::tests::test_bit_string_helper::<BitStringBGI15>();
+    super::tests::test_bit_string_helper::<BitStringBGI16>();
 }
 
 #[test]
 fn test_bad_inputs() {
     super::tests::test_bad_inputs_helper::<BGI15>();
+    super::tests::test_bad_inputs_helper::<BGI16>();
+}
+
+#[test]
+fn test_full_eval() {
+    super::tests::test_full_eval_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_full_eval_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[test]
+fn test_batch_eval() {
+    super::tests::test_batch_eval_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_batch_eval_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[test]
+fn test_eval_range() {
+    super::tests::test_eval_range_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_eval_range_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[test]
+fn test_max_domain_eval() {
+    super::tests::test_max_domain_eval_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_max_domain_eval_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[test]
+fn test_malformed_key() {
+    super::tests::test_malformed_key_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_malformed_key_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[test]
+fn test_key_ownership() {
+    super::tests::test_key_ownership_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_key_ownership_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_eval() {
+    super::tests::test_parallel_eval_helper::<bgi15::Bgi15<F, PRG>>();
+    super::tests::test_parallel_eval_helper::<bgi16::Bgi16<F, PRG>>();
+}
+
+#[test]
+fn test_key_size() {
+    let mut rng = test_rng();
+    let log_domain: usize = 20;
+    let func = (
+        log_domain,
+        rng.gen_range(0..(1 << log_domain)),
+        F::rand(&mut rng),
+    );
+
+    // [BGI16] has a quarter of the codeword seeds of [BGI15]
+    let (bgi15_key, _) = BGI15::gen(&func, &mut rng).unwrap();
+    let (bgi16_key, _) = BGI16::gen(&func, &mut rng).unwrap();
+    let seed_len = <PRG as TreePrg>::Seed::default().len();
+    assert!(bgi15_key.codewords.as_ref().serialized_size() >= 4 * seed_len * (log_domain - 1));
+    assert!(bgi16_key.codewords.as_ref().serialized_size() <= 2 * seed_len * (log_domain - 1));
+
+    // Packing bits into words shrinks the output mask of an early-terminated tree eightfold
+    type Bits = early_termination::Bgi16EarlyTerminationDPF<Xor<bool>, PRG, 128>;
+    type PackedBits = early_termination::Bgi16PackedBitDPF<PRG, 1>;
+    let func = (log_domain, func.1, Xor(true));
+    let (bits_key, _) = Bits::gen(&func, &mut rng).unwrap();
+    let (packed_key, _) = PackedBits::gen(&func, &mut rng).unwrap();
+    assert_eq!(bits_key.log_domain, packed_key.log_domain);
+    assert_eq!(bits_key.mask.serialized_size(), 1 + 128);
+    assert_eq!(packed_key.mask.serialized_size(), 1 + 16);
+    assert!(packed_key.serialized_size() + 100 < bits_key.serialized_size());
 }
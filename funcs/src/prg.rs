@@ -0,0 +1,330 @@
+//! A module providing pseudorandom generators for expanding the seeds of tree-based FSS schemes
+use aes::{
+    cipher::{BlockEncrypt, KeyInit},
+    Aes128, Block,
+};
+use rand::{CryptoRng, Error, Rng, RngCore, SeedableRng};
+use std::sync::OnceLock;
+
+use crate::{Pair, Seed};
+
+/// The expansion of a seed by the `TreePrg` `P`: the seeds and control bits of its two children,
+/// and a stream of material which is independent of them.
+pub type Expansion<P> = (
+    Pair<<P as TreePrg>::Seed>,
+    Pair<bool>,
+    <P as TreePrg>::Material,
+);
+
+/// A length-doubling PRG used to expand the nodes of tree-based FSS schemes.
+///
+/// Each node holds a `Seed`, which is expanded into the seeds and control bits of its two
+/// children, along with an independent stream of material for anything else a scheme stores in
+/// its nodes, e.g. field elements. Implementations are free to derive these however is cheapest,
+/// so long as each output is pseudorandom given the others.
+pub trait TreePrg {
+    /// The seed held by each node of the tree
+    type Seed: Seed;
+
+    /// A stream of further pseudorandom material derived from a seed
+    type Material: CryptoRng + RngCore;
+
+    /// Expands `seed` into the seeds and control bits of its two children, and a stream of
+    /// material which is independent of them.
+    fn expand(seed: &Self::Seed) -> (Pair<Self::Seed>, Pair<bool>, Self::Material);
+
+    /// Expands each of `seeds` as in `expand`, in the same order. Tree-based schemes expand a
+    /// whole level of nodes at once through this method, so implementations which can evaluate
+    /// several seeds in parallel, e.g. by pipelining AES blocks, should override it.
+    fn expand_many(seeds: &[Self::Seed]) -> Vec<Expansion<Self>>
+    where
+        Self: Sized,
+    {
+        seeds.iter().map(Self::expand).collect()
+    }
+
+    /// Derives a stream of material from `seed`. This should only be used for seeds which are
+    /// never passed to `expand`, e.g. those at the leaves of the tree.
+    fn material(seed: &Self::Seed) -> Self::Material;
+}
+
+/// Any seedable `rand` PRG can be used as a `TreePrg` by drawing the children's seeds, then their
+/// control bits, from the PRG's output stream, with the rest of the stream used as material.
+impl<R> TreePrg for R
+where
+    R: CryptoRng + RngCore + SeedableRng,
+    R::Seed: Seed,
+{
+    type Seed = R::Seed;
+    type Material = R;
+
+    #[inline]
+    fn expand(seed: &Self::Seed) -> (Pair<Self::Seed>, Pair<bool>, Self::Material) {
+        let mut prg = R::from_seed(*seed);
+
+        let mut seeds = Pair::<Self::Seed>::default();
+        prg.fill_bytes(seeds[0].as_mut());
+        prg.fill_bytes(seeds[1].as_mut());
+
+        let mut control_bits = Pair::<bool>::default();
+        control_bits[0] = prg.gen_bool(0.5);
+        control_bits[1] = prg.gen_bool(0.5);
+
+        (seeds, control_bits, prg)
+    }
+
+    #[inline]
+    fn material(seed: &Self::Seed) -> Self::Material {
+        R::from_seed(*seed)
+    }
+}
+
+/// The fixed AES-128 key used by `AesPrg`. This key is public: security only relies on AES under
+/// a fixed key behaving like a random permutation, so any value works as long as all parties use
+/// the same one.
+const FIXED_KEY: [u8; 16] = *b"fss-funcs/aesprg";
+
+/// Returns the AES-128 cipher keyed with `FIXED_KEY`. The key schedule is only computed once per
+/// process.
+#[inline]
+fn fixed_key_cipher() -> &'static Aes128 {
+    static CIPHER: OnceLock<Aes128> = OnceLock::new();
+    CIPHER.get_or_init(|| Aes128::new(&FIXED_KEY.into()))
+}
+
+/// A PRG based on fixed-key AES-128 in Matyas–Meyer–Oseas mode, i.e. the correlation-robust hash
+/// `H(x) = AES_k(x) ^ x` for a fixed, public key `k`. The `i`-th output block for a seed `s` is
+/// `H(s ^ i)`.
+///
+/// Unlike a PRG which is keyed by its seed, constructing an `AesPrg` doesn't run a key schedule,
+/// which makes it much cheaper to reseed at every node of a tree. AES-NI is used when the CPU
+/// supports it, with a constant-time software implementation used otherwise.
+#[derive(Clone)]
+pub struct AesPrg {
+    seed: u128,
+    counter: u128,
+    buffer: [u8; 16],
+    index: usize,
+}
+
+impl AesPrg {
+    /// Computes the next output block, advancing the counter
+    #[inline]
+    fn next_block(&mut self) -> [u8; 16] {
+        let x = self.seed ^ self.counter;
+        self.counter += 1;
+
+        let mut block = Block::from(x.to_le_bytes());
+        fixed_key_cipher().encrypt_block(&mut block);
+        (u128::from_le_bytes(block.into()) ^ x).to_le_bytes()
+    }
+}
+
+impl SeedableRng for AesPrg {
+    type Seed = [u8; 16];
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self {
+            seed: u128::from_le_bytes(seed),
+            counter: 0,
+            buffer: [0; 16],
+            index: 16,
+        }
+    }
+}
+
+impl RngCore for AesPrg {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        // Serve bytes from the current block, computing a new one whenever it runs out
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.index == self.buffer.len() {
+                self.buffer = self.next_block();
+                self.index = 0;
+            }
+            let n = (self.buffer.len() - self.index).min(dest.len() - filled);
+            dest[filled..filled + n].copy_from_slice(&self.buffer[self.index..self.index + n]);
+            self.index += n;
+            filled += n;
+        }
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for AesPrg {}
+
+/// A `TreePrg` built on `AesPrg` which computes exactly two AES blocks per expansion: one for
+/// each child. The least significant bit of each block is used as the child's control bit and is
+/// cleared in its seed, and the material stream continues from the third block.
+///
+/// This is the expansion used by most DPF implementations. Using `AesPrg` directly as a
+/// `TreePrg` also works, but spends an extra block on the control bits.
+pub struct FixedKeyAes;
+
+impl FixedKeyAes {
+    /// Splits the two blocks computed for a seed into its children's seeds and control bits
+    #[inline]
+    fn children(blocks: Pair<[u8; 16]>) -> (Pair<[u8; 16]>, Pair<bool>) {
+        let mut seeds = blocks;
+        let mut control_bits = Pair::<bool>::default();
+        for i in 0..2 {
+            control_bits[i] = seeds[i][0] & 1 == 1;
+            seeds[i][0] &= !1;
+        }
+        (seeds, control_bits)
+    }
+}
+
+impl TreePrg for FixedKeyAes {
+    type Seed = [u8; 16];
+    type Material = AesPrg;
+
+    #[inline]
+    fn expand(seed: &Self::Seed) -> (Pair<Self::Seed>, Pair<bool>, Self::Material) {
+        let mut material = AesPrg::from_seed(*seed);
+        let blocks = Pair::new(material.next_block(), material.next_block());
+        let (seeds, control_bits) = Self::children(blocks);
+        (seeds, control_bits, material)
+    }
+
+    fn expand_many(seeds: &[Self::Seed]) -> Vec<Expansion<Self>> {
+        // Encrypt the first two blocks of every seed's stream with a single call, which lets the
+        // AES implementation pipeline them
+        let inputs = seeds
+            .iter()
+            .flat_map(|seed| {
+                let x = u128::from_le_bytes(*seed);
+                [x, x ^ 1]
+            })
+            .collect::<Vec<_>>();
+        let mut blocks = inputs
+            .iter()
+            .map(|x| Block::from(x.to_le_bytes()))
+            .collect::<Vec<_>>();
+        fixed_key_cipher().encrypt_blocks(&mut blocks);
+
+        let outputs = blocks
+            .into_iter()
+            .zip(&inputs)
+            .map(|(block, x)| (u128::from_le_bytes(block.into()) ^ x).to_le_bytes())
+            .collect::<Vec<_>>();
+        seeds
+            .iter()
+            .zip(outputs.chunks_exact(2))
+            .map(|(seed, blocks)| {
+                let (seeds, control_bits) = Self::children(Pair::new(blocks[0], blocks[1]));
+
+                // The material stream continues from the third block, as in `expand`
+                let mut material = AesPrg::from_seed(*seed);
+                material.counter = 2;
+                (seeds, control_bits, material)
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn material(seed: &Self::Seed) -> Self::Material {
+        AesPrg::from_seed(*seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+    use rand::{Rng, RngCore, SeedableRng};
+
+    use super::{AesPrg, FixedKeyAes, TreePrg};
+
+    #[test]
+    fn test_aes_prg() {
+        let mut rng = test_rng();
+        let seed: [u8; 16] = rng.gen();
+
+        // The same seed always produces the same stream
+        let mut stream = [0u8; 100];
+        AesPrg::from_seed(seed).fill_bytes(&mut stream);
+        let mut repeated = [0u8; 100];
+        AesPrg::from_seed(seed).fill_bytes(&mut repeated);
+        assert_eq!(stream, repeated);
+
+        // Drawing the stream in unaligned pieces doesn't change it
+        let mut prg = AesPrg::from_seed(seed);
+        let mut pieces = [0u8; 100];
+        let (first, rest) = pieces.split_at_mut(5);
+        let (second, third) = rest.split_at_mut(27);
+        prg.fill_bytes(first);
+        prg.fill_bytes(second);
+        prg.fill_bytes(third);
+        assert_eq!(stream, pieces);
+
+        let mut prg = AesPrg::from_seed(seed);
+        assert_eq!(prg.next_u32().to_le_bytes(), stream[..4]);
+        assert_eq!(prg.next_u64().to_le_bytes(), stream[4..12]);
+
+        // A different seed produces a different stream, and blocks don't repeat
+        let mut other_seed = seed;
+        other_seed[0] ^= 1;
+        let mut other = [0u8; 100];
+        AesPrg::from_seed(other_seed).fill_bytes(&mut other);
+        assert_ne!(stream, other);
+        assert_ne!(stream[..16], stream[16..32]);
+    }
+
+    #[test]
+    fn test_fixed_key_aes() {
+        let mut rng = test_rng();
+        let seed: [u8; 16] = rng.gen();
+
+        // The children are the first two blocks of `AesPrg` with their control bits extracted, and
+        // the material stream picks up where they leave off
+        let mut stream = [0u8; 64];
+        AesPrg::from_seed(seed).fill_bytes(&mut stream);
+        let (seeds, control_bits, mut material) = FixedKeyAes::expand(&seed);
+        for i in 0..2 {
+            let block = &stream[16 * i..16 * (i + 1)];
+            assert_eq!(control_bits[i], block[0] & 1 == 1);
+            assert_eq!(seeds[i][0], block[0] & !1);
+            assert_eq!(seeds[i][1..], block[1..]);
+        }
+        let mut rest = [0u8; 32];
+        material.fill_bytes(&mut rest);
+        assert_eq!(rest, stream[32..]);
+
+        // Leaf material is the full `AesPrg` stream
+        let mut leaf = [0u8; 64];
+        FixedKeyAes::material(&seed).fill_bytes(&mut leaf);
+        assert_eq!(leaf, stream);
+
+        // Expanding many seeds at once is the same as expanding each of them
+        let seeds = (0..5).map(|_| rng.gen()).collect::<Vec<[u8; 16]>>();
+        let expansions = FixedKeyAes::expand_many(&seeds);
+        assert_eq!(expansions.len(), seeds.len());
+        for (seed, (seeds, control_bits, mut material)) in seeds.iter().zip(expansions) {
+            let (expected_seeds, expected_control_bits, mut expected_material) =
+                FixedKeyAes::expand(seed);
+            assert!(seeds == expected_seeds && control_bits == expected_control_bits);
+            assert_eq!(material.next_u64(), expected_material.next_u64());
+        }
+        assert!(FixedKeyAes::expand_many(&[]).is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+//! A module providing the rings which tree-based FSS schemes output shares in
+use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::AbelianGroup;
+
+/// A commutative ring which FSS schemes can output shares in. This is implemented for every
+/// `AbelianGroup` with the required operations, which includes the arkworks fields and `Z2k`.
+pub trait Ring:
+    AbelianGroup
+    + Zero
+    + One
+    + Neg<Output = Self>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + for<'a> Add<&'a Self, Output = Self>
+    + for<'a> Sub<&'a Self, Output = Self>
+    + for<'a> AddAssign<&'a Self>
+    + for<'a> SubAssign<&'a Self>
+    + UniformRand
+{
+}
+
+impl<T> Ring for T where
+    T: AbelianGroup
+        + Zero
+        + One
+        + Neg<Output = Self>
+        + Add<Output = Self>
+        + Sub<Output = Self>
+        + Mul<Output = Self>
+        + AddAssign
+        + SubAssign
+        + MulAssign
+        + for<'a> Add<&'a Self, Output = Self>
+        + for<'a> Sub<&'a Self, Output = Self>
+        + for<'a> AddAssign<&'a Self>
+        + for<'a> SubAssign<&'a Self>
+        + UniformRand
+{
+}
+
+/// The ring of integers modulo `2^k`, where `T` is an unsigned integer type with `k` bits. All
+/// arithmetic wraps around on overflow, matching the rings used by most MPC frameworks.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Z2k<T>(pub T);
+
+macro_rules! impl_z2k {
+    ($($t:ty),*) => {$(
+        impl Add for Z2k<$t> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                Self(self.0.wrapping_add(other.0))
+            }
+        }
+
+        impl<'a> Add<&'a Self> for Z2k<$t> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: &'a Self) -> Self {
+                self + *other
+            }
+        }
+
+        impl Sub for Z2k<$t> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                Self(self.0.wrapping_sub(other.0))
+            }
+        }
+
+        impl<'a> Sub<&'a Self> for Z2k<$t> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: &'a Self) -> Self {
+                self - *other
+            }
+        }
+
+        impl Mul for Z2k<$t> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, other: Self) -> Self {
+                Self(self.0.wrapping_mul(other.0))
+            }
+        }
+
+        impl Neg for Z2k<$t> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self(self.0.wrapping_neg())
+            }
+        }
+
+        impl AddAssign for Z2k<$t> {
+            #[inline]
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<'a> AddAssign<&'a Self> for Z2k<$t> {
+            #[inline]
+            fn add_assign(&mut self, other: &'a Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for Z2k<$t> {
+            #[inline]
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<'a> SubAssign<&'a Self> for Z2k<$t> {
+            #[inline]
+            fn sub_assign(&mut self, other: &'a Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl MulAssign for Z2k<$t> {
+            #[inline]
+            fn mul_assign(&mut self, other: Self) {
+                *self = *self * other;
+            }
+        }
+
+        impl Zero for Z2k<$t> {
+            #[inline]
+            fn zero() -> Self {
+                Self(0)
+            }
+
+            #[inline]
+            fn is_zero(&self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl One for Z2k<$t> {
+            #[inline]
+            fn one() -> Self {
+                Self(1)
+            }
+        }
+
+        impl From<$t> for Z2k<$t> {
+            #[inline]
+            fn from(val: $t) -> Self {
+                Self(val)
+            }
+        }
+
+        impl Distribution<Z2k<$t>> for Standard {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Z2k<$t> {
+                Z2k(rng.gen())
+            }
+        }
+
+        impl AbelianGroup for Z2k<$t> {
+            #[inline]
+            fn group_zero() -> Self {
+                Self(0)
+            }
+
+            #[inline]
+            fn group_add(&self, other: &Self) -> Self {
+                *self + other
+            }
+
+            #[inline]
+            fn group_neg(&self) -> Self {
+                -*self
+            }
+
+            #[inline]
+            fn group_sample<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                Self(rng.gen())
+            }
+
+            #[inline]
+            fn group_sub(&self, other: &Self) -> Self {
+                *self - other
+            }
+        }
+
+        impl Serialize for Z2k<$t> {
+            #[inline]
+            fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+                Ok(writer.write_all(&self.0.to_le_bytes())?)
+            }
+
+            #[inline]
+            fn serialized_size(&self) -> usize {
+                std::mem::size_of::<$t>()
+            }
+        }
+
+        impl Deserialize for Z2k<$t> {
+            #[inline]
+            fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+                let mut bytes = [0u8; std::mem::size_of::<$t>()];
+                reader.read_exact(&mut bytes)?;
+                Ok(Self(<$t>::from_le_bytes(bytes)))
+            }
+        }
+    )*};
+}
+
+impl_z2k!(u8, u16, u32, u64, u128);
+
+#[cfg(test)]
+mod tests {
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::test_rng;
+    use rand::Rng;
+
+    use super::{Ring, Z2k};
+
+    fn assert_ring<R: Ring>() {}
+
+    #[test]
+    fn test_z2k() {
+        assert_ring::<Z2k<u8>>();
+        assert_ring::<Z2k<u16>>();
+        assert_ring::<Z2k<u32>>();
+        assert_ring::<Z2k<u64>>();
+        assert_ring::<Z2k<u128>>();
+
+        // Arithmetic wraps around
+        let max = Z2k::<u64>(u64::MAX);
+        assert_eq!(max + Z2k(2), Z2k(1));
+        assert_eq!(Z2k(1u64) - Z2k(2), max);
+        assert_eq!(-Z2k(1u64), max);
+        assert_eq!(max * max, Z2k(1));
+
+        // Serialization round-trips with a fixed size
+        let mut rng = test_rng();
+        let x = Z2k::<u128>(rng.gen());
+        let mut serialized = vec![0; x.serialized_size()];
+        x.serialize(&mut serialized[..]).unwrap();
+        assert_eq!(serialized.len(), 16);
+        assert_eq!(Z2k::<u128>::deserialize(serialized.as_slice()).unwrap(), x);
+    }
+}
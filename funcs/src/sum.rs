@@ -0,0 +1,267 @@
+//! A module providing an FSS scheme for sums of functions, e.g. sparse vectors
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, ops::Range, vec::Vec};
+
+use crate::{
+    domain::domain_size,
+    interval::bgi15::Bgi15DIF,
+    point::bgi15::Bgi15DPF,
+    tree::{TreeFSS, TreeKey, TreeScheme},
+    AbelianGroup, TreePrg, FSS,
+};
+
+/// FSS scheme for sums of point functions, i.e. multi-point functions, using a DPF key based on
+/// [[BGI15]] for each point.
+///
+/// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
+pub type Bgi15SumDPF<F, P> = SumFSS<F, Bgi15DPF<F, P>>;
+
+/// FSS scheme for sums of interval functions, i.e. step functions, using a DIF key based on
+/// [[BGI15]] for each interval.
+///
+/// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
+pub type Bgi15SumDIF<G, P> = SumFSS<G, Bgi15DIF<G, P>>;
+
+/// An FSS scheme for the sum of several functions of the FSS scheme `T`, whose shares are
+/// additive in the group `G`. A key is a vector of keys of `T`, one for each function, and a
+/// party's share is the sum of its shares of each function.
+///
+/// Keys and evaluation grow linearly with the number of functions, so this is a simple baseline
+/// for multi-point and multi-interval functions rather than a compact scheme.
+pub struct SumFSS<G, T>
+where
+    G: AbelianGroup,
+    T: FSS<Range = G, Share = G>,
+{
+    _group: PhantomData<G>,
+    _fss: PhantomData<T>,
+}
+
+impl<G, T> FSS for SumFSS<G, T>
+where
+    G: AbelianGroup,
+    T: FSS<Range = G, Share = G>,
+{
+    type Key = Vec<T::Key>;
+    type Description = Vec<T::Description>;
+    type Domain = T::Domain;
+    type Range = G;
+    type Share = G;
+
+    fn gen<RNG: CryptoRng + RngCore>(
+        f: &Self::Description,
+        rng: &mut RNG,
+    ) -> Result<(Self::Key, Self::Key), Box<dyn Error>> {
+        if f.is_empty() {
+            return Err("SumFSS(): There must be at least one function".into());
+        }
+
+        let mut p1_keys = Vec::with_capacity(f.len());
+        let mut p2_keys = Vec::with_capacity(f.len());
+        for func in f {
+            let (p1_key, p2_key) = T::gen(func, rng)?;
+            p1_keys.push(p1_key);
+            p2_keys.push(p2_key);
+        }
+        Ok((p1_keys, p2_keys))
+    }
+
+    fn eval(key: &Self::Key, point: &Self::Domain) -> Result<G, Box<dyn Error>> {
+        key.iter().try_fold(G::group_zero(), |sum, k| {
+            Ok(sum.group_add(&T::eval(k, point)?))
+        })
+    }
+
+    fn decode(shares: (&G, &G)) -> Result<G, Box<dyn Error>> {
+        Ok(shares.0.group_sub(shares.1))
+    }
+}
+
+impl<G, P, T> SumFSS<G, TreeScheme<G, P, T>>
+where
+    G: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<G, P, Domain = usize>,
+{
+    /// Takes a `Key` as input, and outputs a secret share of the underlying function at every
+    /// point in the domain, ordered by point. Every function must have the same domain.
+    pub fn full_eval(key: &[TreeKey<G, P, T>]) -> Result<Vec<G>, Box<dyn Error>> {
+        Self::eval_range(key, 0..domain_size(Self::log_domain(key)?)?)
+    }
+
+    /// Takes a `Key` and a range of points as input, and outputs a secret share of the underlying
+    /// function at each point in `range`, ordered by point.
+    pub fn eval_range(
+        key: &[TreeKey<G, P, T>],
+        range: Range<usize>,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        Self::sum(key, range.len(), |k| {
+            TreeScheme::<G, P, T>::eval_range(k, range.clone())
+        })
+    }
+
+    /// Takes a `Key` and a batch of points as input, and outputs a secret share of the underlying
+    /// function at each point, in the same order as `points`.
+    pub fn batch_eval(
+        key: &[TreeKey<G, P, T>],
+        points: &[usize],
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        Self::sum(key, points.len(), |k| {
+            TreeScheme::<G, P, T>::batch_eval(k, points)
+        })
+    }
+
+    /// Outputs the logarithm of the domain size of the keys in `key`
+    fn log_domain(key: &[TreeKey<G, P, T>]) -> Result<usize, Box<dyn Error>> {
+        Ok(key
+            .first()
+            .ok_or("Eval(): Key has no functions")?
+            .log_domain)
+    }
+
+    /// Sums the `len` shares output by `eval` for each of the keys in `key`, after ensuring that
+    /// the keys have the same domain
+    fn sum(
+        key: &[TreeKey<G, P, T>],
+        len: usize,
+        eval: impl Fn(&TreeKey<G, P, T>) -> Result<Vec<G>, Box<dyn Error>>,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        if key.windows(2).any(|k| k[0].log_domain != k[1].log_domain) {
+            return Err("Eval(): Keys must have the same domain".into());
+        }
+
+        let mut shares = vec![G::group_zero(); len];
+        for k in key {
+            shares
+                .iter_mut()
+                .zip(eval(k)?)
+                .for_each(|(s, k_s)| *s = s.group_add(&k_s));
+        }
+        Ok(shares)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<G, P, T> SumFSS<G, TreeScheme<G, P, T>>
+where
+    G: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<G, P, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+{
+    /// A multi-threaded version of `full_eval`. See `TreeScheme::par_full_eval`.
+    pub fn par_full_eval(
+        key: &[TreeKey<G, P, T>],
+        split_depth: usize,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        Self::par_eval_range(key, 0..domain_size(Self::log_domain(key)?)?, split_depth)
+    }
+
+    /// A multi-threaded version of `eval_range`. See `TreeScheme::par_eval_range`.
+    pub fn par_eval_range(
+        key: &[TreeKey<G, P, T>],
+        range: Range<usize>,
+        split_depth: usize,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        Self::sum(key, range.len(), |k| {
+            TreeScheme::<G, P, T>::par_eval_range(k, range.clone(), split_depth)
+        })
+    }
+
+    /// A multi-threaded version of `batch_eval`. See `TreeScheme::par_batch_eval`.
+    pub fn par_batch_eval(
+        key: &[TreeKey<G, P, T>],
+        points: &[usize],
+        split_depth: usize,
+    ) -> Result<Vec<G>, Box<dyn Error>> {
+        Self::sum(key, points.len(), |k| {
+            TreeScheme::<G, P, T>::par_batch_eval(k, points, split_depth)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+    use rand::{seq::index::sample, Rng};
+    use rand_chacha::ChaChaRng;
+
+    use super::{Bgi15SumDIF, SumFSS};
+    use crate::{point::bgi16::Bgi16DPF, AbelianGroup, Z2k, FSS};
+
+    type R = Z2k<u64>;
+    type SumDPF = SumFSS<R, Bgi16DPF<R, ChaChaRng>>;
+    type SumDIF = Bgi15SumDIF<R, ChaChaRng>;
+
+    #[test]
+    fn test_sums() {
+        let mut rng = test_rng();
+
+        for log_domain in 2usize..10 {
+            // A sparse vector with up to 8 non-zero points, and a step function with as many steps
+            let t = rng.gen_range(1..=8.min(1 << log_domain));
+            let points = sample(&mut rng, 1 << log_domain, t).into_vec();
+            let vals = (0..t)
+                .map(|_| R::group_sample(&mut rng))
+                .collect::<Vec<_>>();
+            let func = points.iter().zip(&vals).map(|(x, y)| (log_domain, *x, *y));
+            let (p1_dpf, p2_dpf) = SumDPF::gen(&func.clone().collect(), &mut rng).unwrap();
+            let (p1_dif, p2_dif) = SumDIF::gen(&func.collect(), &mut rng).unwrap();
+            assert_eq!(p1_dpf.len(), t);
+
+            let p1_results = SumDPF::full_eval(&p1_dpf).unwrap();
+            let p2_results = SumDPF::full_eval(&p2_dpf).unwrap();
+            for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+                assert_eq!(*p1_result, SumDPF::eval(&p1_dpf, &p).unwrap());
+                let expected = points
+                    .iter()
+                    .zip(&vals)
+                    .filter(|(x, _)| **x == p)
+                    .fold(R::group_zero(), |sum, (_, y)| sum.group_add(y));
+                assert_eq!(SumDPF::decode((p1_result, p2_result)).unwrap(), expected);
+            }
+
+            let p1_results = SumDIF::full_eval(&p1_dif).unwrap();
+            let p2_results = SumDIF::full_eval(&p2_dif).unwrap();
+            for (p, (p1_result, p2_result)) in p1_results.iter().zip(&p2_results).enumerate() {
+                assert_eq!(*p1_result, SumDIF::eval(&p1_dif, &p).unwrap());
+                let expected = points
+                    .iter()
+                    .zip(&vals)
+                    .filter(|(x, _)| p < **x)
+                    .fold(R::group_zero(), |sum, (_, y)| sum.group_add(y));
+                assert_eq!(SumDIF::decode((p1_result, p2_result)).unwrap(), expected);
+            }
+        }
+
+        // Points of the same function can coincide, in which case their values are summed
+        let y = R::group_sample(&mut rng);
+        let func = vec![(4, 3, y), (4, 3, y)];
+        let (p1_key, p2_key) = SumDPF::gen(&func, &mut rng).unwrap();
+        let p1_result = SumDPF::eval(&p1_key, &3).unwrap();
+        let p2_result = SumDPF::eval(&p2_key, &3).unwrap();
+        assert_eq!(
+            SumDPF::decode((&p1_result, &p2_result)).unwrap(),
+            y.group_add(&y)
+        );
+
+        #[cfg(feature = "parallel")]
+        assert_eq!(
+            SumDPF::par_full_eval(&p1_key, 2).unwrap(),
+            SumDPF::full_eval(&p1_key).unwrap()
+        );
+
+        // There must be at least one function
+        assert!(SumDPF::gen(&vec![], &mut rng).is_err());
+
+        // Functions with different domains can be evaluated at single points, but not expanded
+        let func = vec![(4, 3, y), (5, 20, y)];
+        let (p1_key, _) = SumDPF::gen(&func, &mut rng).unwrap();
+        assert!(SumDPF::eval(&p1_key, &20).is_err());
+        assert!(SumDPF::eval(&p1_key, &3).is_ok());
+        assert!(SumDPF::full_eval(&p1_key).is_err());
+        assert!(SumDPF::batch_eval(&p1_key, &[3, 20]).is_err());
+    }
+}
@@ -0,0 +1,28 @@
+use ark_ff::{BigInteger64 as BigInteger, FftParameters, Fp64, Fp64Parameters, FpParameters};
+
+/// A field to use in tests. This is the same 63-bit field used in
+/// "Lightweight Techniques for Private Heavy Hitters"
+pub(crate) type F = Fp64<FParameters>;
+
+pub(crate) struct FParameters;
+
+impl Fp64Parameters for FParameters {}
+impl FftParameters for FParameters {
+    type BigInt = BigInteger;
+    const TWO_ADICITY: u32 = 1;
+    const TWO_ADIC_ROOT_OF_UNITY: Self::BigInt = BigInteger([1]);
+}
+
+impl FpParameters for FParameters {
+    const MODULUS: BigInteger = BigInteger([9223372036854775783]);
+    const MODULUS_BITS: u32 = 63u32;
+    const REPR_SHAVE_BITS: u32 = 1;
+    const R: BigInteger = BigInteger([50]);
+    const R2: BigInteger = BigInteger([2500]);
+    const INV: u64 = 1106804644422573097;
+    const GENERATOR: BigInteger = BigInteger([3]);
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+    const T: BigInteger = BigInteger([4611686018427387891]);
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([2305843009213693945]);
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([4611686018427387891]);
+}
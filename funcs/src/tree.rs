@@ -2,34 +2,41 @@
 //! outlined in [[BGI15]].
 //!
 //! [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
-use ark_ff::Field;
 use ark_serialize::{CanonicalDeserialize as Deserialize, CanonicalSerialize as Serialize, *};
-use rand::{CryptoRng, RngCore, SeedableRng};
-use std::{error::Error, marker::PhantomData, rc::Rc, vec::Vec};
+use rand::{CryptoRng, RngCore};
+use std::{error::Error, marker::PhantomData, ops::Range, sync::Arc, vec::Vec};
 
-use crate::{Seed, FSS};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    domain::{domain_size, last_point},
+    AbelianGroup, Expansion, TreeDomain, TreePrg, FSS,
+};
 
 /// An interface for the 2-party FSS scheme following the binary-tree-based PRG approach
-/// introduced in [[BGI15]].
+/// introduced in [[BGI15]]. Each node of the tree is expanded using the `TreePrg` `P`.
 ///
 /// [BGI15]: https://www.iacr.org/archive/eurocrypt2015/90560300/90560300.pdf
-pub trait TreeFSS<F, PRG, S>
+pub trait TreeFSS<F, P>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
+    F: AbelianGroup,
+    P: TreePrg,
 {
     /// Description of the underlying function being secret-shared
     type Description;
 
+    /// The domain of the underlying function
+    type Domain: TreeDomain;
+
     /// The root node of the tree. This can contain more information than other nodes in the tree.
-    type Root: Serialize + Deserialize;
+    type Root: Clone + Serialize + Deserialize;
 
     /// A node in the tree
-    type Node;
+    type Node: Clone;
 
     /// A 'hint' given at each level of the tree to ensure correctness of the output
-    type Codeword: Serialize + Deserialize;
+    type Codeword: Clone + Serialize + Deserialize;
 
     /// A node in the tree when evaluating and the exact path being traversed is known. This allows
     /// some additional memory optimizations by not storing information that won't be used.
@@ -49,16 +56,23 @@ where
     /// Evaluates the root node at the provided bit and returns an `EvaluationNode`
     fn evaluate_root(bit: bool, root: &Self::Root) -> (Self::EvaluationNode, Option<F>);
 
-    /// Using an `EvaluationNode`, sample the corresponding masked node the tree.
-    fn sample_masked_level(node: &Self::EvaluationNode) -> Self::Node;
+    /// The seed held by an `EvaluationNode`, which is expanded to sample the next level
+    fn seed(node: &Self::EvaluationNode) -> &P::Seed;
 
-    /// Compute the codeword for the provided masked node
+    /// Using an `EvaluationNode` and the expansion of its seed, sample the corresponding masked
+    /// node the tree.
+    fn sample_masked_level(node: &Self::EvaluationNode, expansion: Expansion<P>) -> Self::Node;
+
+    /// Compute the codeword for the provided masked node. For schemes which use an accumulator,
+    /// `accumulated` is the difference of the parties' accumulators along the path being
+    /// secret-shared, i.e. what they would decode to before this level.
     fn compute_codeword<RNG: CryptoRng + RngCore>(
         f: &Self::Description,
         bit: bool,
         p1_node: &Self::EvaluationNode,
         p1_masked_node: &Self::Node,
         p2_masked_node: &Self::Node,
+        accumulated: Option<F>,
         rng: &mut RNG,
     ) -> Self::Codeword;
 
@@ -71,58 +85,149 @@ where
         accumulator: Option<&mut F>,
     ) -> Self::EvaluationNode;
 
-    /// Sample a field element from a tree leaf
-    fn compute_output_elem(node: &Self::EvaluationNode) -> Option<F>;
-
-    /// Compute a mask for the output field elements
+    /// Compute a mask for the output group elements from the leaf each party reaches when
+    /// evaluating the path being secret-shared, and the difference of their accumulators
     fn compute_mask(
         f: &Self::Description,
-        p1_output_elem: &Option<F>,
-        p2_output_elem: &Option<F>,
+        p1_node: &Self::EvaluationNode,
+        p2_node: &Self::EvaluationNode,
+        accumulated: Option<F>,
     ) -> Result<Option<F>, Box<dyn Error>>;
+
+    /// Compute a party's output share from the leaf it reached, the mask, and its accumulator
+    fn compute_output(
+        node: &Self::EvaluationNode,
+        mask: Option<&F>,
+        accumulator: Option<F>,
+    ) -> Result<F, Box<dyn Error>>;
 }
 
-/// An `FSS` key for `TreeFSS` schemes
-#[derive(Clone, Serialize, Deserialize)]
-pub struct TreeKey<F, PRG, S, T>
+/// An `FSS` key for `TreeFSS` schemes.
+///
+/// Both keys output by `gen` share a single copy of the codewords, which are identical for each
+/// party. Keys are `Send + Sync` whenever the scheme's nodes and codewords are, so they can be
+/// freely shared between threads. Before handing a key to a party, e.g. by moving it to another
+/// thread that should not keep the other party's key alive, call `into_owned` to give it an
+/// independent copy of the codewords. Serializing a key always writes out the codewords in full.
+pub struct TreeKey<F, P, T>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
-    T: TreeFSS<F, PRG, S>,
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P>,
 {
     pub log_domain: usize,
     pub root: T::Root,
-    pub codewords: Rc<Vec<T::Codeword>>,
+    pub codewords: Arc<Vec<T::Codeword>>,
     pub mask: Option<F>,
 }
 
+impl<F, P, T> TreeKey<F, P, T>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P>,
+{
+    /// Returns a key which is the sole owner of its codewords. The codewords are only copied if
+    /// they are currently shared with another key.
+    pub fn into_owned(mut self) -> Self {
+        Arc::make_mut(&mut self.codewords);
+        self
+    }
+
+    /// Returns `true` if this key is the sole owner of its codewords.
+    pub fn is_owned(&self) -> bool {
+        Arc::strong_count(&self.codewords) == 1
+    }
+
+    /// Returns `true` if the domain contains at least two points, and there is a codeword for
+    /// every level of the tree below the root.
+    fn is_well_formed(&self) -> bool {
+        self.log_domain > 0 && self.codewords.len() == self.log_domain - 1
+    }
+}
+
+// Cloning a key is cheap since the codewords are shared rather than copied
+impl<F, P, T> Clone for TreeKey<F, P, T>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            log_domain: self.log_domain,
+            root: self.root.clone(),
+            codewords: self.codewords.clone(),
+            mask: self.mask,
+        }
+    }
+}
+
+// The codewords are serialized in place, so the encoding is the same as if `TreeKey` owned them
+impl<F, P, T> Serialize for TreeKey<F, P, T>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P>,
+{
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.log_domain.serialize(&mut writer)?;
+        self.root.serialize(&mut writer)?;
+        self.codewords.as_ref().serialize(&mut writer)?;
+        self.mask.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.log_domain.serialized_size()
+            + self.root.serialized_size()
+            + self.codewords.as_ref().serialized_size()
+            + self.mask.serialized_size()
+    }
+}
+
+impl<F, P, T> Deserialize for TreeKey<F, P, T>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P>,
+{
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let key = Self {
+            log_domain: usize::deserialize(&mut reader)?,
+            root: T::Root::deserialize(&mut reader)?,
+            codewords: Arc::new(Vec::<T::Codeword>::deserialize(&mut reader)?),
+            mask: Option::<F>::deserialize(&mut reader)?,
+        };
+        if !key.is_well_formed() {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(key)
+    }
+}
+
 /// Wrapper struct that implements `FSS` on any type that implements `TreeFSS`.
 ///
 /// TODO: Explore replacing this with a macro
-pub struct TreeScheme<F, PRG, S, T>
+pub struct TreeScheme<F, P, T>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
-    T: TreeFSS<F, PRG, S>,
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P>,
 {
     _f: PhantomData<F>,
-    _prg: PhantomData<PRG>,
-    _s: PhantomData<S>,
+    _prg: PhantomData<P>,
     _t: PhantomData<T>,
 }
 
-impl<F, PRG, S, T> FSS for TreeScheme<F, PRG, S, T>
+impl<F, P, T> FSS for TreeScheme<F, P, T>
 where
-    F: Field,
-    PRG: CryptoRng + RngCore + SeedableRng<Seed = S>,
-    S: Seed,
-    T: TreeFSS<F, PRG, S>,
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P>,
 {
-    type Key = TreeKey<F, PRG, S, T>;
+    type Key = TreeKey<F, P, T>;
     type Description = T::Description;
-    type Domain = usize;
+    type Domain = T::Domain;
     type Range = F;
     type Share = F;
 
@@ -141,13 +246,13 @@ where
         all_codewords.reserve_exact(log_domain - 1);
 
         // Begin evaluating the tree defined by the root along `point`
-        let (mut p1_node, _) = T::evaluate_root(point[0], &p1_root);
-        let (mut p2_node, _) = T::evaluate_root(point[0], &p2_root);
+        let (mut p1_node, mut p1_accumulator) = T::evaluate_root(point[0], &p1_root);
+        let (mut p2_node, mut p2_accumulator) = T::evaluate_root(point[0], &p2_root);
 
         for i in 0..(log_domain - 1) {
             // Use the seed from the current `EvaluationNode` to sample the next level of the tree
-            let p1_masked_node = T::sample_masked_level(&p1_node);
-            let p2_masked_node = T::sample_masked_level(&p2_node);
+            let p1_masked_node = T::sample_masked_level(&p1_node, P::expand(T::seed(&p1_node)));
+            let p2_masked_node = T::sample_masked_level(&p2_node, P::expand(T::seed(&p2_node)));
 
             // Calculate the codeword for this level of the tree
             let codewords = T::compute_codeword(
@@ -156,29 +261,43 @@ where
                 &p1_node,
                 &p1_masked_node,
                 &p2_masked_node,
+                Self::accumulated(p1_accumulator, p2_accumulator),
                 rng,
             );
 
             // Use the codewords to compute the nodes for the next level of the tree
-            p1_node =
-                T::compute_next_level(point[i + 1], &p1_node, p1_masked_node, &codewords, None);
+            p1_node = T::compute_next_level(
+                point[i + 1],
+                &p1_node,
+                p1_masked_node,
+                &codewords,
+                p1_accumulator.as_mut(),
+            );
 
-            p2_node =
-                T::compute_next_level(point[i + 1], &p2_node, p2_masked_node, &codewords, None);
+            p2_node = T::compute_next_level(
+                point[i + 1],
+                &p2_node,
+                p2_masked_node,
+                &codewords,
+                p2_accumulator.as_mut(),
+            );
 
             all_codewords.push(codewords);
         }
 
-        // TODO: Optionally compute final field elem
-        let p1_elem = T::compute_output_elem(&p1_node);
-        let p2_elem = T::compute_output_elem(&p2_node);
-        let mask = T::compute_mask(f, &p1_elem, &p2_elem)?;
+        // Compute the mask for the output using the final node of each party
+        let mask = T::compute_mask(
+            f,
+            &p1_node,
+            &p2_node,
+            Self::accumulated(p1_accumulator, p2_accumulator),
+        )?;
 
         // Construct and return the resulting keys
         let key_1 = Self::Key {
             log_domain,
             root: p1_root,
-            codewords: Rc::new(all_codewords),
+            codewords: Arc::new(all_codewords),
             mask,
         };
 
@@ -193,8 +312,10 @@ where
     }
 
     fn eval(key: &Self::Key, point: &Self::Domain) -> Result<F, Box<dyn Error>> {
+        Self::check_key(key)?;
+
         // Bit-decompose the input point
-        let point = crate::usize_to_bits(key.log_domain, *point)?;
+        let point = point.to_bits(key.log_domain)?;
 
         // Iterate through each layer of the tree, using the current node to generate new
         // masked nodes and select the correct codeword, and the codeword to unmask the
@@ -205,7 +326,7 @@ where
         let (mut node, mut accumulator) = T::evaluate_root(point[0], &key.root);
         for i in 1..key.log_domain {
             // Use the seed from the current `EvaluationNode` to sample the next level of the tree
-            let masked_node = T::sample_masked_level(&node);
+            let masked_node = T::sample_masked_level(&node, P::expand(T::seed(&node)));
 
             // Combine the masked node and codeword to get the next node and update the accumulator
             node = T::compute_next_level(
@@ -216,18 +337,386 @@ where
                 accumulator.as_mut(),
             );
         }
-        if let Some(mask) = key.mask {
-            // TODO
-            let elem = T::compute_output_elem(&node).unwrap();
-            Ok(elem * mask)
-        } else if let Some(accum) = accumulator {
-            Ok(accum)
-        } else {
-            unreachable!()
-        }
+        T::compute_output(&node, key.mask.as_ref(), accumulator)
     }
 
     fn decode(shares: (&Self::Share, &Self::Share)) -> Result<Self::Range, Box<dyn Error>> {
-        Ok(*shares.0 - shares.1)
+        // Shares are additive in the output group, e.g. they are XORed for `Xor` groups
+        Ok(shares.0.group_sub(shares.1))
+    }
+}
+
+/// The depth of the subtrees which are expanded a level at a time by `expand_subtree`, bounding the
+/// number of nodes held in memory at once by `2^BATCH_DEPTH`
+const BATCH_DEPTH: usize = 12;
+
+/// A node in the tree, along with the leftmost leaf below it and the accumulator value on the
+/// path to it. Evaluation of the leaves below a `Subtree` is independent of the rest of the tree.
+struct Subtree<F, N> {
+    start: usize,
+    node: N,
+    accumulator: Option<F>,
+}
+
+/// A `Subtree` paired with the slice of shares its leaves should be written to
+type RangeTask<'a, F, N> = (Subtree<F, N>, &'a mut [F]);
+
+/// A `Subtree` paired with the sorted points it contains, and the slice of shares those points
+/// should be written to
+type BatchTask<'a, 'b, F, N> = (Subtree<F, N>, &'b [(usize, usize)], &'a mut [F]);
+
+impl<F, P, T> TreeScheme<F, P, T>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P>,
+{
+    /// The difference of the parties' accumulators during `gen`, if the scheme uses one.
+    #[inline]
+    fn accumulated(p1_accumulator: Option<F>, p2_accumulator: Option<F>) -> Option<F> {
+        p1_accumulator
+            .zip(p2_accumulator)
+            .map(|(p1_acc, p2_acc)| p1_acc.group_sub(&p2_acc))
+    }
+
+    /// Ensures that `key` has a non-trivial domain and a codeword for each level of its tree.
+    fn check_key(key: &TreeKey<F, P, T>) -> Result<(), Box<dyn Error>> {
+        if !key.is_well_formed() {
+            return Err("Key is malformed".into());
+        }
+        Ok(())
+    }
+}
+
+/// Evaluation of many points at once is only supported for domains indexed by `usize`, since
+/// larger domains are too large to expand.
+impl<F, P, T> TreeScheme<F, P, T>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P, Domain = usize>,
+{
+    /// Takes a `Key` as input, and outputs a secret share of the underlying function at every
+    /// point in the domain, ordered by point.
+    ///
+    /// Rather than calling `eval` for each point, this walks the tree so that every internal node
+    /// is expanded exactly once, batching the expansion of nodes at the same level.
+    pub fn full_eval(key: &TreeKey<F, P, T>) -> Result<Vec<F>, Box<dyn Error>> {
+        Self::eval_range(key, 0..domain_size(key.log_domain)?)
+    }
+
+    /// Takes a `Key` and a range of points as input, and outputs a secret share of the underlying
+    /// function at each point in `range`, ordered by point.
+    ///
+    /// Only the subtrees which cover `range` are expanded, and each of their internal nodes is
+    /// expanded exactly once.
+    pub fn eval_range(
+        key: &TreeKey<F, P, T>,
+        range: Range<usize>,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        Self::check_range(key, &range)?;
+        let mut shares = vec![F::group_zero(); range.len()];
+        for (subtree, slot) in Self::split_range(key, 1, &range, &mut shares) {
+            Self::expand_subtree(key, 1, subtree, &range, slot)?;
+        }
+        Ok(shares)
+    }
+
+    /// Takes a `Key` and a batch of points as input, and outputs a secret share of the underlying
+    /// function at each point, in the same order as `points`.
+    ///
+    /// The points are sorted so that those sharing a prefix in the tree are evaluated together,
+    /// meaning each node in the trie of queried points is only expanded once.
+    pub fn batch_eval(key: &TreeKey<F, P, T>, points: &[usize]) -> Result<Vec<F>, Box<dyn Error>> {
+        let sorted_points = Self::sort_points(key, points)?;
+        let mut sorted_shares = vec![F::group_zero(); points.len()];
+        for (subtree, subset, slot) in Self::split_batch(key, 1, &sorted_points, &mut sorted_shares)
+        {
+            Self::batch_subtree(key, 1, subtree, subset, slot)?;
+        }
+        Ok(Self::unsort_shares(&sorted_points, sorted_shares))
+    }
+
+    /// Ensures that `range` is valid in the domain of `key`.
+    fn check_range(key: &TreeKey<F, P, T>, range: &Range<usize>) -> Result<(), Box<dyn Error>> {
+        Self::check_key(key)?;
+        let last = last_point(key.log_domain)?;
+        if range.start > range.end || range.end.saturating_sub(1) > last {
+            return Err("Input range is not contained in provided domain".into());
+        }
+        Ok(())
+    }
+
+    /// Ensures that `points` are valid in the domain of `key`, and sorts them while remembering
+    /// the position each was queried at.
+    fn sort_points(
+        key: &TreeKey<F, P, T>,
+        points: &[usize],
+    ) -> Result<Vec<(usize, usize)>, Box<dyn Error>> {
+        Self::check_key(key)?;
+        let last = last_point(key.log_domain)?;
+        let mut sorted_points = Vec::with_capacity(points.len());
+        for (i, point) in points.iter().enumerate() {
+            if *point > last {
+                return Err("Input point is not contained in provided domain".into());
+            }
+            sorted_points.push((*point, i));
+        }
+        sorted_points.sort_unstable();
+        Ok(sorted_points)
+    }
+
+    /// Moves each share in `sorted_shares` back to the position its point was queried at.
+    fn unsort_shares(sorted_points: &[(usize, usize)], sorted_shares: Vec<F>) -> Vec<F> {
+        let mut shares = vec![F::group_zero(); sorted_shares.len()];
+        sorted_points
+            .iter()
+            .zip(sorted_shares)
+            .for_each(|((_, i), share)| shares[*i] = share);
+        shares
+    }
+
+    /// Walks the tree down to depth `depth`, and returns every node at that depth whose leaves
+    /// `[first, last]` satisfy `keep(first, last)`, ordered from left to right. Subtrees which
+    /// aren't kept are never expanded.
+    fn subtrees(
+        key: &TreeKey<F, P, T>,
+        depth: usize,
+        keep: impl Fn(usize, usize) -> bool,
+    ) -> Vec<Subtree<F, T::EvaluationNode>> {
+        let size = 1 << (key.log_domain - 1);
+        let mut subtrees = Vec::new();
+        for (bit, start) in [(false, 0), (true, size)] {
+            if keep(start, start + (size - 1)) {
+                let (node, accumulator) = T::evaluate_root(bit, &key.root);
+                subtrees.push(Subtree {
+                    start,
+                    node,
+                    accumulator,
+                });
+            }
+        }
+        Self::expand_levels(key, 1..depth, subtrees, &keep)
+    }
+
+    /// Expands `subtrees`, which live at depth `levels.start`, one level at a time down to depth
+    /// `levels.end`, keeping only the nodes whose leaves `[first, last]` satisfy
+    /// `keep(first, last)`, and returns those at the last level from left to right. The seeds of
+    /// each level are expanded with a single call to `TreePrg::expand_many`.
+    fn expand_levels(
+        key: &TreeKey<F, P, T>,
+        levels: Range<usize>,
+        mut subtrees: Vec<Subtree<F, T::EvaluationNode>>,
+        keep: &impl Fn(usize, usize) -> bool,
+    ) -> Vec<Subtree<F, T::EvaluationNode>> {
+        for level in levels {
+            let half = 1 << (key.log_domain - level - 1);
+            let seeds = subtrees
+                .iter()
+                .map(|subtree| *T::seed(&subtree.node))
+                .collect::<Vec<_>>();
+            let mut children = Vec::with_capacity(2 * subtrees.len());
+            for (subtree, expansion) in subtrees.into_iter().zip(P::expand_many(&seeds)) {
+                // Both children are derived from the same masked node, so only sample it once
+                let masked_node = T::sample_masked_level(&subtree.node, expansion);
+                for (bit, start) in [(false, subtree.start), (true, subtree.start + half)] {
+                    if keep(start, start + (half - 1)) {
+                        let mut accumulator = subtree.accumulator;
+                        let node = T::compute_next_level(
+                            bit,
+                            &subtree.node,
+                            masked_node.clone(),
+                            &key.codewords[level - 1],
+                            accumulator.as_mut(),
+                        );
+                        children.push(Subtree {
+                            start,
+                            node,
+                            accumulator,
+                        });
+                    }
+                }
+            }
+            subtrees = children;
+        }
+        subtrees
+    }
+
+    /// Returns the subtrees at depth `depth` which intersect `range`, each paired with the slice
+    /// of `shares` its leaves in `range` should be written to.
+    fn split_range<'a>(
+        key: &TreeKey<F, P, T>,
+        depth: usize,
+        range: &Range<usize>,
+        mut shares: &'a mut [F],
+    ) -> Vec<RangeTask<'a, F, T::EvaluationNode>> {
+        let size = 1 << (key.log_domain - depth);
+        Self::subtrees(key, depth, Self::intersects_range(range))
+            .into_iter()
+            .map(|subtree| {
+                let last = (range.end - 1).min(subtree.start + (size - 1));
+                let len = last + 1 - range.start.max(subtree.start);
+                let (slot, rest) = std::mem::take(&mut shares).split_at_mut(len);
+                shares = rest;
+                (subtree, slot)
+            })
+            .collect()
+    }
+
+    /// Returns the subtrees at depth `depth` which contain one of `sorted_points`, each paired
+    /// with the points it contains and the slice of `sorted_shares` their shares should be
+    /// written to.
+    fn split_batch<'a, 'b>(
+        key: &TreeKey<F, P, T>,
+        depth: usize,
+        mut sorted_points: &'b [(usize, usize)],
+        mut sorted_shares: &'a mut [F],
+    ) -> Vec<BatchTask<'a, 'b, F, T::EvaluationNode>> {
+        let size = 1 << (key.log_domain - depth);
+        Self::subtrees(key, depth, Self::contains_point(sorted_points))
+            .into_iter()
+            .map(|subtree| {
+                let len = sorted_points.partition_point(|(p, _)| *p <= subtree.start + (size - 1));
+                let (subset, rest) = sorted_points.split_at(len);
+                sorted_points = rest;
+                let (slot, rest) = std::mem::take(&mut sorted_shares).split_at_mut(len);
+                sorted_shares = rest;
+                (subtree, subset, slot)
+            })
+            .collect()
+    }
+
+    /// Returns whether the leaves `[first, last]` intersect `range`. Leaves are bounded
+    /// inclusively so that the last leaf of a domain of `2^64` points doesn't overflow.
+    fn intersects_range(range: &Range<usize>) -> impl Fn(usize, usize) -> bool + '_ {
+        |first, last| range.start <= last && first < range.end
+    }
+
+    /// Returns whether one of `sorted_points` lies in the leaves `[first, last]`
+    fn contains_point(sorted_points: &[(usize, usize)]) -> impl Fn(usize, usize) -> bool + '_ {
+        |first, last| {
+            let i = sorted_points.partition_point(|(p, _)| *p < first);
+            i < sorted_points.len() && sorted_points[i].0 <= last
+        }
+    }
+
+    /// Expands `subtree`, which lives at depth `level`, and writes the shares of its leaves in
+    /// `range` to `shares` from left to right.
+    ///
+    /// The subtree is expanded one level at a time so that each level's seeds are expanded in a
+    /// single batch. To bound the number of nodes held at once, large subtrees are first split
+    /// into subtrees of at most `2^BATCH_DEPTH` leaves, which are expanded in turn.
+    fn expand_subtree(
+        key: &TreeKey<F, P, T>,
+        level: usize,
+        subtree: Subtree<F, T::EvaluationNode>,
+        range: &Range<usize>,
+        shares: &mut [F],
+    ) -> Result<(), Box<dyn Error>> {
+        let intersects_range = Self::intersects_range(range);
+        let depth = key.log_domain.saturating_sub(BATCH_DEPTH).max(level);
+        let mut shares = shares;
+        for subtree in Self::expand_levels(key, level..depth, vec![subtree], &intersects_range) {
+            let leaves =
+                Self::expand_levels(key, depth..key.log_domain, vec![subtree], &intersects_range);
+            let (slot, rest) = std::mem::take(&mut shares).split_at_mut(leaves.len());
+            shares = rest;
+            for (share, leaf) in slot.iter_mut().zip(leaves) {
+                *share = T::compute_output(&leaf.node, key.mask.as_ref(), leaf.accumulator)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates the sorted `points` which all pass through `subtree` at depth `level`, writing
+    /// their shares to `shares` in the same order.
+    ///
+    /// Only the nodes on the paths to `points` are expanded, one level at a time so that each
+    /// level's seeds are expanded in a single batch.
+    fn batch_subtree(
+        key: &TreeKey<F, P, T>,
+        level: usize,
+        subtree: Subtree<F, T::EvaluationNode>,
+        points: &[(usize, usize)],
+        shares: &mut [F],
+    ) -> Result<(), Box<dyn Error>> {
+        let leaves = Self::expand_levels(
+            key,
+            level..key.log_domain,
+            vec![subtree],
+            &Self::contains_point(points),
+        );
+
+        // Every leaf holds at least one point, so just copy its share for duplicates
+        let mut i = 0;
+        for leaf in leaves {
+            let share = T::compute_output(&leaf.node, key.mask.as_ref(), leaf.accumulator)?;
+            while i < points.len() && points[i].0 == leaf.start {
+                shares[i] = share;
+                i += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<F, P, T> TreeScheme<F, P, T>
+where
+    F: AbelianGroup,
+    P: TreePrg,
+    T: TreeFSS<F, P, Domain = usize>,
+    T::Root: Sync,
+    T::Codeword: Send + Sync,
+    T::EvaluationNode: Send,
+{
+    /// A multi-threaded version of `full_eval`.
+    ///
+    /// The tree is expanded sequentially down to depth `split_depth`, after which each of the (up
+    /// to) `2^split_depth` subtrees is expanded on the rayon thread pool. `split_depth` should be
+    /// large enough to give each thread several subtrees, but is capped at `log_domain`.
+    pub fn par_full_eval(
+        key: &TreeKey<F, P, T>,
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        Self::par_eval_range(key, 0..domain_size(key.log_domain)?, split_depth)
+    }
+
+    /// A multi-threaded version of `eval_range`, splitting the tree at `split_depth` as in
+    /// `par_full_eval`.
+    pub fn par_eval_range(
+        key: &TreeKey<F, P, T>,
+        range: Range<usize>,
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        Self::check_range(key, &range)?;
+        let depth = split_depth.clamp(1, key.log_domain);
+        let mut shares = vec![F::group_zero(); range.len()];
+        Self::split_range(key, depth, &range, &mut shares)
+            .into_par_iter()
+            .map(|(subtree, slot)| {
+                Self::expand_subtree(key, depth, subtree, &range, slot).map_err(|e| e.to_string())
+            })
+            .collect::<Result<(), String>>()?;
+        Ok(shares)
+    }
+
+    /// A multi-threaded version of `batch_eval`, splitting the tree at `split_depth` as in
+    /// `par_full_eval`.
+    pub fn par_batch_eval(
+        key: &TreeKey<F, P, T>,
+        points: &[usize],
+        split_depth: usize,
+    ) -> Result<Vec<F>, Box<dyn Error>> {
+        let sorted_points = Self::sort_points(key, points)?;
+        let depth = split_depth.clamp(1, key.log_domain);
+        let mut sorted_shares = vec![F::group_zero(); points.len()];
+        Self::split_batch(key, depth, &sorted_points, &mut sorted_shares)
+            .into_par_iter()
+            .map(|(subtree, subset, slot)| {
+                Self::batch_subtree(key, depth, subtree, subset, slot).map_err(|e| e.to_string())
+            })
+            .collect::<Result<(), String>>()?;
+        Ok(Self::unsort_shares(&sorted_points, sorted_shares))
     }
 }